@@ -10,11 +10,12 @@ use rust_game::surface::{BlendMode, Surface, SurfaceBuilder};
 use rust_game_sdl2::context::Sdl2Context;
 use std::error::Error;
 
-pub struct FrameToHSVColor {}
+// cycles the hue in OKLCh, which keeps the perceived lightness constant
+pub struct FrameToHueColor {}
 
-impl ToColor<u32> for FrameToHSVColor {
+impl ToColor<u32> for FrameToHueColor {
     fn get_color(&mut self, frame: u32) -> Box<dyn Color> {
-        Box::new(ColorU8::from_hsl((frame as u16 + 1) % 360, 100, 50))
+        Box::new(ColorU8::from_oklch(0.7, 0.12, ((frame + 1) % 360) as f32))
     }
 }
 
@@ -55,17 +56,17 @@ pub fn main() {
     let mut sprite_group = Group::new(vec![
         DefaultSprite::new_animated(
             smiley_surface.clone().unwrap(),
-            Some(Box::new(ColorAnimation::new(0, Box::new(FrameToHSVColor {})))),
+            Some(Box::new(ColorAnimation::new(0, Box::new(FrameToHueColor {})))),
             Some(Box::new(HypotrochoidAnimation::new(0.0, 1.0, (400, 300), (100.0, 60.0, 100.0)))),
         ),
         DefaultSprite::new_animated(
             smiley_surface.clone().unwrap(),
-            Some(Box::new(ColorAnimation::new(120, Box::new(FrameToHSVColor {})))),
+            Some(Box::new(ColorAnimation::new(120, Box::new(FrameToHueColor {})))),
             Some(Box::new(HypotrochoidAnimation::new(360.0 * 3.0 / 4.0, 1.0, (400, 300), (100.0, 60.0, 100.0)))),
         ),
         DefaultSprite::new_animated(
             smiley_surface.clone().unwrap(),
-            Some(Box::new(ColorAnimation::new(240, Box::new(FrameToHSVColor {})))),
+            Some(Box::new(ColorAnimation::new(240, Box::new(FrameToHueColor {})))),
             Some(Box::new(HypotrochoidAnimation::new(360.0 * 3.0 / 4.0 * 2.0, 1.0, (400, 300), (100.0, 60.0, 100.0)))),
         ),
         DefaultSprite::new_animated(
@@ -1,3 +1,4 @@
+use rust_game::canvas::BackgroundMode;
 use rust_game::color::ColorU8;
 use rust_game::context::Context;
 use rust_game::draw::DrawUtility;
@@ -15,12 +16,11 @@ pub fn main() {
     let time = context.time().unwrap();
     let mut clock = time.new_clock();
 
-    let mut background_surf = Sdl2Context::new_surface_alpha(canvas.get_surface().get_size()).unwrap();
-    let (w, h) = background_surf.get_size();
     let colors = vec![ColorU8::new_gray(96), ColorU8::new_gray(64)];
     let ts: i32 = 60;
-    for x in 0..(w as i32 + ts - 1) / ts {
-        for y in 0..(h as i32 + ts - 1) / ts {
+    let mut background_surf = Sdl2Context::new_surface_alpha((ts as u32 * 2, ts as u32 * 2)).unwrap();
+    for x in 0..2 {
+        for y in 0..2 {
             draw.rectangle(background_surf.as_mut(), false, &colors[((x + y) % 2) as usize], Rect::new(x * ts, y * ts, ts, ts), -1)
                 .unwrap();
         }
@@ -42,7 +42,7 @@ pub fn main() {
             }
         }
 
-        canvas.get_surface().blit(background_surf.as_ref(), (0, 0), BlendMode::Blend).unwrap();
+        canvas.blit_background(background_surf.as_ref(), BackgroundMode::Tile { offset: (0, 0) }).unwrap();
 
         draw.rectangle(canvas.get_surface(), true, &ColorU8::from_hue(0), Rect::new(50, 50, 50, 50), -1).unwrap();
         draw.circle(canvas.get_surface(), true, &ColorU8::from_hue(30), (175, 75), 25, -1).unwrap();
@@ -64,18 +64,18 @@ pub fn main() {
             &context,
             canvas.get_surface(),
             true,
-            &ColorU8::from_hue_alpha(0, 25),
+            &ColorU8::from_hue_alpha(0, 64),
             Rect::new(50, 250, 50, 50),
             BlendMode::Blend,
         )
         .unwrap();
-        DrawUtility::blit_circle(&context, canvas.get_surface(), true, &ColorU8::from_hue_alpha(30, 25), (175, 275), 25, BlendMode::Blend)
+        DrawUtility::blit_circle(&context, canvas.get_surface(), true, &ColorU8::from_hue_alpha(30, 64), (175, 275), 25, BlendMode::Blend)
             .unwrap();
         DrawUtility::blit_ellipse(
             &context,
             canvas.get_surface(),
             true,
-            &ColorU8::from_hue_alpha(60, 25),
+            &ColorU8::from_hue_alpha(60, 64),
             (275, 275),
             (25, 15),
             angle as f32,
@@ -86,7 +86,7 @@ pub fn main() {
             &context,
             canvas.get_surface(),
             true,
-            &ColorU8::from_hue_alpha(120, 25),
+            &ColorU8::from_hue_alpha(120, 64),
             (375, 275),
             25,
             0.0..angle as f32,
@@ -97,7 +97,7 @@ pub fn main() {
             &context,
             canvas.get_surface(),
             true,
-            &ColorU8::from_hue_alpha(180, 25),
+            &ColorU8::from_hue_alpha(180, 64),
             (475, 275),
             (25, 15),
             angle as f32,
@@ -109,7 +109,7 @@ pub fn main() {
             &context,
             canvas.get_surface(),
             true,
-            &ColorU8::from_hue_alpha(240, 25),
+            &ColorU8::from_hue_alpha(240, 64),
             &vec![(550, 300), (600, 300), (575, 250)],
             BlendMode::Blend,
         )
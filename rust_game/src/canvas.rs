@@ -1,16 +1,93 @@
-use crate::surface::Surface;
-use std::error::Error;
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::surface::{BlendMode, Surface};
+use crate::transform;
+
+/// How `Canvas::blit_background` places the background surface on the canvas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackgroundMode {
+    /// Unscaled in the center of the canvas.
+    Center,
+    /// Scaled to the size of the canvas, the aspect ratio is not kept.
+    Stretch,
+    /// Scaled to fit into the canvas and centered, the regions beside the background are not changed.
+    Fit,
+    /// Scaled to cover the canvas and centered, the background is cropped.
+    Fill,
+    /// Repeated, `offset` scrolls the tiles.
+    Tile { offset: (i32, i32) },
+}
+
+// rectangle of the scaled background in the canvas, `Tile` gives the rectangle of the first tile
+fn background_rect(canvas_rect: &Rect, size: (u32, u32), mode: BackgroundMode) -> Rect {
+    let rect = Rect::new(0, 0, size.0 as i32, size.1 as i32);
+    match mode {
+        BackgroundMode::Center => Rect::new_center(canvas_rect.get_center(), rect.get_size()),
+        BackgroundMode::Stretch => canvas_rect.clone(),
+        BackgroundMode::Fit => rect.fit(canvas_rect),
+        BackgroundMode::Fill => {
+            let ratio = (canvas_rect.w as f32 / rect.w as f32).max(canvas_rect.h as f32 / rect.h as f32);
+            let size = ((rect.w as f32 * ratio).round() as i32, (rect.h as f32 * ratio).round() as i32);
+            Rect::new_center(canvas_rect.get_center(), size)
+        }
+        BackgroundMode::Tile { offset } => {
+            let x = offset.0.rem_euclid(rect.w);
+            let y = offset.1.rem_euclid(rect.h);
+            rect.move_(if x > 0 { x - rect.w } else { x }, if y > 0 { y - rect.h } else { y })
+        }
+    }
+}
 
 pub trait Canvas {
     fn get_surface<'a>(&'a mut self) -> &'a mut dyn Surface;
-    fn update(&mut self) -> Result<(), Box<dyn Error>>;
+    fn update(&mut self) -> Result<(), GameError>;
+
+    /// Presents only the regions of the canvas surface which are covered by the rectangles.
+    ///
+    /// The default implementation presents the whole surface.
+    fn update_rects(&mut self, _rects: &[Rect]) -> Result<(), GameError> {
+        self.update()
+    }
+
+    /// Copies the background surface to the canvas surface, placed and scaled according to the mode,
+    /// and returns the changed rectangle.
+    ///
+    /// `Stretch`, `Fit` and `Fill` scale the background with `transform::smooth_scale` on every call,
+    /// a background which is drawn every frame should be scaled once in advance and blitted with `Center`.
+    fn blit_background(&mut self, background: &dyn Surface, mode: BackgroundMode) -> Result<Rect, GameError> {
+        let canvas_surface = self.get_surface();
+        let canvas_rect = canvas_surface.get_rect();
+        if background.get_width() == 0 || background.get_height() == 0 || canvas_rect.w <= 0 || canvas_rect.h <= 0 {
+            return Ok(Rect::new(0, 0, 0, 0));
+        }
+        let rect = background_rect(&canvas_rect, background.get_size(), mode);
+        match mode {
+            BackgroundMode::Center => canvas_surface.blit(background, rect.get_top_left(), BlendMode::None),
+            BackgroundMode::Stretch | BackgroundMode::Fit | BackgroundMode::Fill => {
+                let scaled = transform::smooth_scale(background, (rect.w.max(1) as u32, rect.h.max(1) as u32))?;
+                canvas_surface.blit(scaled.as_ref(), rect.get_top_left(), BlendMode::None)
+            }
+            BackgroundMode::Tile { .. } => {
+                for y in (rect.y..canvas_rect.h).step_by(rect.h as usize) {
+                    for x in (rect.x..canvas_rect.w).step_by(rect.w as usize) {
+                        canvas_surface.blit(background, (x, y), BlendMode::None)?;
+                    }
+                }
+                Ok(canvas_rect)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod canvas_test {
     use super::*;
     use crate::color::{Color, ColorU8};
+    use crate::headless::canvas::{FrameCapture, FrameStore, HeadlessCanvas};
+    use crate::surface::software_surface::SoftwareSurface;
     use crate::test::SurfaceMock;
+    use std::cell::RefCell;
+    use std::rc::Rc;
 
     struct CanvasMock {
         pub fill_color: ColorU8,
@@ -36,7 +113,7 @@ mod canvas_test {
         fn get_surface<'a>(&'a mut self) -> &'a mut dyn Surface {
             &mut self.canvas_surface
         }
-        fn update(&mut self) -> Result<(), Box<dyn Error>> {
+        fn update(&mut self) -> Result<(), GameError> {
             self.canvas_color = self.fill_color.clone();
             Ok(())
         }
@@ -56,4 +133,108 @@ mod canvas_test {
         canvas.update().unwrap();
         assert_eq!(ColorU8::new(128, 32, 64, 255), canvas.canvas_color);
     }
+
+    fn background() -> SoftwareSurface {
+        let mut background = SoftwareSurface::new((4, 2));
+        background.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        background.set_at((0, 0), &ColorU8::new_rgb(0, 0, 255)).unwrap();
+        background
+    }
+
+    fn headless_canvas(size: (u32, u32)) -> HeadlessCanvas {
+        let frame_store = FrameStore {
+            frame_capture: FrameCapture::Discard,
+            frame_count: 0,
+            frames: Vec::new(),
+        };
+        HeadlessCanvas::new(size, Rc::new(RefCell::new(frame_store)))
+    }
+
+    fn covered(canvas: &mut HeadlessCanvas) -> usize {
+        canvas.get_surface().raw().unwrap().chunks_exact(4).filter(|p| p[3] > 0).count()
+    }
+
+    fn rect_tuple(rect: &Rect) -> (i32, i32, i32, i32) {
+        (rect.x, rect.y, rect.w, rect.h)
+    }
+
+    #[test]
+    fn test_background_rect() {
+        let canvas_rect = Rect::new(0, 0, 10, 10);
+        assert_eq!((3, 4, 4, 2), rect_tuple(&background_rect(&canvas_rect, (4, 2), BackgroundMode::Center)));
+        assert_eq!((0, 0, 10, 10), rect_tuple(&background_rect(&canvas_rect, (4, 2), BackgroundMode::Stretch)));
+        assert_eq!((0, 2, 10, 5), rect_tuple(&background_rect(&canvas_rect, (4, 2), BackgroundMode::Fit)));
+        assert_eq!((-5, 0, 20, 10), rect_tuple(&background_rect(&canvas_rect, (4, 2), BackgroundMode::Fill)));
+        let tile = BackgroundMode::Tile { offset: (0, 0) };
+        assert_eq!((0, 0, 4, 2), rect_tuple(&background_rect(&canvas_rect, (4, 2), tile)));
+        let tile = BackgroundMode::Tile { offset: (5, -3) };
+        assert_eq!((-3, -1, 4, 2), rect_tuple(&background_rect(&canvas_rect, (4, 2), tile)));
+    }
+
+    #[test]
+    fn test_blit_background_center() {
+        let mut canvas = headless_canvas((10, 10));
+        let rect = canvas.blit_background(&background(), BackgroundMode::Center).unwrap();
+        assert_eq!((3, 4, 4, 2), rect_tuple(&rect));
+        assert_eq!(8, covered(&mut canvas));
+        assert_eq!(ColorU8::new_rgb(0, 0, 255), canvas.get_surface().get_at((3, 4)).unwrap());
+    }
+
+    #[test]
+    fn test_blit_background_stretch() {
+        let mut canvas = headless_canvas((8, 8));
+        let rect = canvas.blit_background(&background(), BackgroundMode::Stretch).unwrap();
+        assert_eq!((0, 0, 8, 8), rect_tuple(&rect));
+        assert_eq!(64, covered(&mut canvas));
+        assert_eq!(ColorU8::new_rgb(255, 0, 0), canvas.get_surface().get_at((7, 7)).unwrap());
+    }
+
+    #[test]
+    fn test_blit_background_fit() {
+        let mut canvas = headless_canvas((8, 8));
+        let rect = canvas.blit_background(&background(), BackgroundMode::Fit).unwrap();
+        assert_eq!((0, 2, 8, 4), rect_tuple(&rect));
+        assert_eq!(32, covered(&mut canvas));
+        assert_eq!(ColorU8::new_rgb(0, 0, 255), canvas.get_surface().get_at((0, 2)).unwrap());
+    }
+
+    #[test]
+    fn test_blit_background_fill() {
+        let mut canvas = headless_canvas((8, 8));
+        let rect = canvas.blit_background(&background(), BackgroundMode::Fill).unwrap();
+        assert_eq!((0, 0, 8, 8), rect_tuple(&rect));
+        assert_eq!(64, covered(&mut canvas));
+        assert_eq!(ColorU8::new_rgb(255, 0, 0), canvas.get_surface().get_at((2, 0)).unwrap());
+    }
+
+    #[test]
+    fn test_blit_background_tile() {
+        let mut canvas = headless_canvas((6, 5));
+        let rect = canvas.blit_background(&background(), BackgroundMode::Tile { offset: (0, 0) }).unwrap();
+        assert_eq!((0, 0, 6, 5), rect_tuple(&rect));
+        assert_eq!(30, covered(&mut canvas));
+        for position in [(0, 0), (4, 0), (0, 2), (4, 4)] {
+            assert_eq!(ColorU8::new_rgb(0, 0, 255), canvas.get_surface().get_at(position).unwrap());
+        }
+        canvas.blit_background(&background(), BackgroundMode::Tile { offset: (1, -1) }).unwrap();
+        assert_eq!(ColorU8::new_rgb(0, 0, 255), canvas.get_surface().get_at((1, 1)).unwrap());
+        assert_eq!(ColorU8::new_rgb(255, 0, 0), canvas.get_surface().get_at((0, 0)).unwrap());
+        assert_eq!(ColorU8::new_rgb(0, 0, 255), canvas.get_surface().get_at((5, 3)).unwrap());
+    }
+
+    #[test]
+    fn test_blit_background_empty() {
+        let mut canvas = headless_canvas((4, 4));
+        let rect = canvas.blit_background(&SoftwareSurface::new((0, 0)), BackgroundMode::Tile { offset: (0, 0) }).unwrap();
+        assert_eq!((0, 0), rect.get_size());
+        assert_eq!(0, covered(&mut canvas));
+    }
+
+    #[test]
+    fn test_update_rects() {
+        let mut canvas = CanvasMock::new();
+        canvas.fill(&ColorU8::new_rgb(128, 32, 64));
+        canvas.update_rects(&[Rect::new(0, 0, 1, 1)]).unwrap();
+        assert_eq!(ColorU8::new(128, 32, 64, 255), canvas.canvas_color);
+    }
 }
@@ -0,0 +1,373 @@
+use crate::rectangle::Rect;
+
+#[derive(Clone)]
+pub struct Circle {
+    pub x: i32,
+    pub y: i32,
+    pub r: i32,
+}
+
+impl Circle {
+    pub fn new(x: i32, y: i32, r: i32) -> Circle {
+        Circle { x, y, r }
+    }
+
+    pub fn new_center(center: (i32, i32), radius: i32) -> Circle {
+        Circle {
+            x: center.0,
+            y: center.1,
+            r: radius,
+        }
+    }
+
+    pub fn new_from_rect(rect: &Rect) -> Circle {
+        Circle::new_center(rect.get_center(), rect.get_width().min(rect.get_height()) / 2)
+    }
+
+    pub fn get_radius(&self) -> i32 {
+        self.r
+    }
+
+    pub fn set_radius(&mut self, radius: i32) {
+        self.r = radius;
+    }
+
+    pub fn get_diameter(&self) -> i32 {
+        self.r * 2
+    }
+
+    pub fn set_diameter(&mut self, diameter: i32) {
+        self.r = diameter / 2;
+    }
+
+    pub fn get_center_x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn set_center_x(&mut self, center_x: i32) {
+        self.x = center_x;
+    }
+
+    pub fn get_center_y(&self) -> i32 {
+        self.y
+    }
+
+    pub fn set_center_y(&mut self, center_y: i32) {
+        self.y = center_y;
+    }
+
+    pub fn get_center(&self) -> (i32, i32) {
+        (self.x, self.y)
+    }
+
+    pub fn set_center(&mut self, center: (i32, i32)) {
+        self.x = center.0;
+        self.y = center.1;
+    }
+
+    pub fn get_rect(&self) -> Rect {
+        Rect::new(self.x - self.r, self.y - self.r, self.r * 2, self.r * 2)
+    }
+
+    pub fn move_(&self, x: i32, y: i32) -> Circle {
+        Circle::new(self.x + x, self.y + y, self.r)
+    }
+
+    pub fn move_ip(&mut self, x: i32, y: i32) {
+        self.x += x;
+        self.y += y;
+    }
+
+    // inflates the radius, the center is kept
+    pub fn inflate(&self, r: i32) -> Circle {
+        Circle::new(self.x, self.y, self.r + r)
+    }
+
+    pub fn inflate_ip(&mut self, r: i32) {
+        self.r += r;
+    }
+
+    pub fn update(&mut self, x: i32, y: i32, radius: i32) {
+        self.x = x;
+        self.y = y;
+        self.r = radius;
+    }
+
+    pub fn clamp(&self, rect: &Rect) -> Circle {
+        let center = self.get_rect().clamp(rect).get_center();
+        Circle::new(center.0, center.1, self.r)
+    }
+
+    pub fn clamp_ip(&mut self, rect: &Rect) {
+        let center = self.get_rect().clamp(rect).get_center();
+        self.x = center.0;
+        self.y = center.1;
+    }
+
+    pub fn contains(&self, circle: &Circle) -> bool {
+        let (dx, dy) = ((circle.x - self.x) as i64, (circle.y - self.y) as i64);
+        let dr = (self.r - circle.r) as i64;
+        dr >= 0 && dx * dx + dy * dy <= dr * dr
+    }
+
+    pub fn contains_rect(&self, rect: &Rect) -> bool {
+        let (left, top, right, bottom) = pixel_bounds(rect);
+        [(left, top), (right, top), (left, bottom), (right, bottom)].iter().all(|&(x, y)| self.collide_point(x, y))
+    }
+
+    // the circle includes its boundary, so touching shapes collide
+    pub fn collide_point(&self, x: i32, y: i32) -> bool {
+        let (dx, dy) = ((x - self.x) as i64, (y - self.y) as i64);
+        dx * dx + dy * dy <= self.r as i64 * self.r as i64
+    }
+
+    pub fn collide_circle(&self, circle: &Circle) -> bool {
+        let (dx, dy) = ((circle.x - self.x) as i64, (circle.y - self.y) as i64);
+        let r = (self.r + circle.r) as i64;
+        dx * dx + dy * dy <= r * r
+    }
+
+    pub fn collide_rect(&self, rect: &Rect) -> bool {
+        let (left, top, right, bottom) = pixel_bounds(rect);
+        let (dx, dy) = ((self.x.clamp(left, right) - self.x) as i64, (self.y.clamp(top, bottom) - self.y) as i64);
+        dx * dx + dy * dy <= self.r as i64 * self.r as i64
+    }
+}
+
+// inclusive bounds of the pixels of the normalized rectangle, an empty rectangle is reduced to its position
+fn pixel_bounds(rect: &Rect) -> (i32, i32, i32, i32) {
+    let mut rect = rect.clone();
+    rect.normalize();
+    let right = (rect.get_right() - 1).max(rect.get_left());
+    let bottom = (rect.get_bottom() - 1).max(rect.get_top());
+    (rect.get_left(), rect.get_top(), right, bottom)
+}
+
+#[cfg(test)]
+mod circle_test {
+    use super::*;
+
+    fn assert_equal_circle(circle: &Circle, x: i32, y: i32, r: i32) {
+        assert!(circle.x == x);
+        assert!(circle.y == y);
+        assert!(circle.r == r);
+    }
+
+    fn assert_equal_rect(rect: &Rect, x: i32, y: i32, w: i32, h: i32) {
+        assert!(rect.x == x);
+        assert!(rect.y == y);
+        assert!(rect.w == w);
+        assert!(rect.h == h);
+    }
+
+    #[test]
+    fn new_test() {
+        let circle = Circle::new(1, 2, 3);
+        assert_equal_circle(&circle, 1, 2, 3);
+    }
+
+    #[test]
+    fn new_center_test() {
+        let circle = Circle::new_center((1, 2), 3);
+        assert_equal_circle(&circle, 1, 2, 3);
+    }
+
+    #[test]
+    fn new_from_rect_test() {
+        assert_equal_circle(&Circle::new_from_rect(&Rect::new(10, 20, 10, 10)), 15, 25, 5);
+        assert_equal_circle(&Circle::new_from_rect(&Rect::new(10, 20, 20, 10)), 20, 25, 5);
+    }
+
+    #[test]
+    fn copy_test() {
+        let circle = Circle::new(1, 2, 3).clone();
+        assert_equal_circle(&circle, 1, 2, 3);
+    }
+
+    #[test]
+    fn get_radius_test() {
+        let circle = Circle::new(4, 5, 6);
+        assert!(circle.get_radius() == 6);
+    }
+
+    #[test]
+    fn set_radius_test() {
+        let mut circle = Circle::new(4, 5, 6);
+        circle.set_radius(1);
+        assert_equal_circle(&circle, 4, 5, 1);
+    }
+
+    #[test]
+    fn get_diameter_test() {
+        let circle = Circle::new(4, 5, 6);
+        assert!(circle.get_diameter() == 12);
+    }
+
+    #[test]
+    fn set_diameter_test() {
+        let mut circle = Circle::new(4, 5, 6);
+        circle.set_diameter(4);
+        assert_equal_circle(&circle, 4, 5, 2);
+    }
+
+    #[test]
+    fn get_center_x_test() {
+        let circle = Circle::new(4, 5, 6);
+        assert!(circle.get_center_x() == 4);
+    }
+
+    #[test]
+    fn set_center_x_test() {
+        let mut circle = Circle::new(4, 5, 6);
+        circle.set_center_x(1);
+        assert_equal_circle(&circle, 1, 5, 6);
+    }
+
+    #[test]
+    fn get_center_y_test() {
+        let circle = Circle::new(4, 5, 6);
+        assert!(circle.get_center_y() == 5);
+    }
+
+    #[test]
+    fn set_center_y_test() {
+        let mut circle = Circle::new(4, 5, 6);
+        circle.set_center_y(1);
+        assert_equal_circle(&circle, 4, 1, 6);
+    }
+
+    #[test]
+    fn get_center_test() {
+        let circle = Circle::new(4, 5, 6);
+        assert!(circle.get_center() == (4, 5));
+    }
+
+    #[test]
+    fn set_center_test() {
+        let mut circle = Circle::new(4, 5, 6);
+        circle.set_center((11, 12));
+        assert_equal_circle(&circle, 11, 12, 6);
+    }
+
+    #[test]
+    fn get_rect_test() {
+        let rect = Circle::new(10, 20, 5).get_rect();
+        assert_equal_rect(&rect, 5, 15, 10, 10);
+    }
+
+    #[test]
+    fn move_test() {
+        let circle = Circle::new(0, 0, 1).move_(5, 10);
+        assert_equal_circle(&circle, 5, 10, 1);
+    }
+
+    #[test]
+    fn move_ip_test() {
+        let mut circle = Circle::new(0, 0, 1);
+        circle.move_ip(5, 10);
+        assert_equal_circle(&circle, 5, 10, 1);
+    }
+
+    #[test]
+    fn inflate_test() {
+        let circle = Circle::new(1, 2, 3).inflate(4);
+        assert_equal_circle(&circle, 1, 2, 7);
+    }
+
+    #[test]
+    fn inflate_ip_test() {
+        let mut circle = Circle::new(1, 2, 3);
+        circle.inflate_ip(-2);
+        assert_equal_circle(&circle, 1, 2, 1);
+    }
+
+    #[test]
+    fn update_test() {
+        let mut circle = Circle::new(0, 0, 0);
+        circle.update(1, 2, 3);
+        assert_equal_circle(&circle, 1, 2, 3);
+    }
+
+    #[test]
+    fn clamp_test() {
+        let circle1 = Circle::new(10, 30, 5).clamp(&Rect::new(20, 20, 100, 100));
+        assert_equal_circle(&circle1, 25, 30, 5);
+        let circle2 = Circle::new(30, 10, 5).clamp(&Rect::new(20, 20, 100, 100));
+        assert_equal_circle(&circle2, 30, 25, 5);
+        let circle3 = Circle::new(200, 30, 5).clamp(&Rect::new(20, 20, 100, 100));
+        assert_equal_circle(&circle3, 115, 30, 5);
+        let circle4 = Circle::new(30, 200, 5).clamp(&Rect::new(20, 20, 100, 100));
+        assert_equal_circle(&circle4, 30, 115, 5);
+        let circle5 = Circle::new(50, 50, 5).clamp(&Rect::new(20, 20, 100, 100));
+        assert_equal_circle(&circle5, 50, 50, 5);
+    }
+
+    #[test]
+    fn clamp_ip_test() {
+        let mut circle1 = Circle::new(10, 30, 5);
+        circle1.clamp_ip(&Rect::new(20, 20, 100, 100));
+        assert_equal_circle(&circle1, 25, 30, 5);
+        let mut circle2 = Circle::new(200, 200, 5);
+        circle2.clamp_ip(&Rect::new(20, 20, 100, 100));
+        assert_equal_circle(&circle2, 115, 115, 5);
+    }
+
+    #[test]
+    fn contains_test() {
+        assert!(Circle::new(10, 10, 10).contains(&Circle::new(10, 10, 10)));
+        assert!(Circle::new(10, 10, 10).contains(&Circle::new(12, 10, 8)));
+        assert!(!Circle::new(10, 10, 10).contains(&Circle::new(13, 10, 8)));
+        assert!(!Circle::new(10, 10, 10).contains(&Circle::new(10, 10, 11)));
+    }
+
+    #[test]
+    fn contains_rect_test() {
+        assert!(Circle::new(10, 10, 10).contains_rect(&Rect::new(5, 5, 10, 10)));
+        assert!(!Circle::new(10, 10, 10).contains_rect(&Rect::new(0, 0, 20, 20)));
+        assert!(Circle::new(10, 10, 5).contains_rect(&Rect::new(7, 6, 7, 9)));
+        assert!(!Circle::new(10, 10, 5).contains_rect(&Rect::new(7, 6, 8, 9)));
+        assert!(Circle::new(10, 10, 5).contains_rect(&Rect::new(14, 15, -7, -9)));
+        assert!(Circle::new(10, 10, 0).contains_rect(&Rect::new(10, 10, 1, 1)));
+    }
+
+    #[test]
+    fn collide_point_test() {
+        assert!(Circle::new(10, 10, 5).collide_point(10, 10));
+        assert!(Circle::new(10, 10, 5).collide_point(15, 10));
+        assert!(Circle::new(10, 10, 5).collide_point(10, 5));
+        assert!(Circle::new(10, 10, 5).collide_point(13, 14));
+        assert!(!Circle::new(10, 10, 5).collide_point(14, 14));
+        assert!(!Circle::new(10, 10, 5).collide_point(16, 10));
+        assert!(!Circle::new(10, 10, 5).collide_point(10, 4));
+    }
+
+    #[test]
+    fn collide_circle_test() {
+        assert!(!Circle::new(10, 10, 5).collide_circle(&Circle::new(21, 10, 5)));
+        assert!(Circle::new(10, 10, 5).collide_circle(&Circle::new(20, 10, 5)));
+        assert!(Circle::new(10, 10, 5).collide_circle(&Circle::new(19, 10, 5)));
+        assert!(Circle::new(10, 10, 5).collide_circle(&Circle::new(17, 17, 5)));
+        assert!(!Circle::new(10, 10, 5).collide_circle(&Circle::new(18, 18, 5)));
+        assert!(Circle::new(10, 10, 10).collide_circle(&Circle::new(10, 10, 1)));
+        assert!(Circle::new(10, 10, 5).collide_circle(&Circle::new(15, 10, 0)));
+        assert!(Circle::new(10, 10, 0).collide_circle(&Circle::new(10, 10, 0)));
+    }
+
+    #[test]
+    fn collide_rect_test() {
+        assert!(!Circle::new(10, 10, 5).collide_rect(&Rect::new(16, 0, 10, 20)));
+        assert!(Circle::new(10, 10, 5).collide_rect(&Rect::new(15, 0, 10, 20)));
+        assert!(Circle::new(10, 10, 5).collide_rect(&Rect::new(0, 0, 6, 20)));
+        assert!(!Circle::new(10, 10, 5).collide_rect(&Rect::new(0, 0, 5, 20)));
+        assert!(Circle::new(10, 10, 5).collide_rect(&Rect::new(13, 13, 10, 10)));
+        assert!(!Circle::new(10, 10, 5).collide_rect(&Rect::new(14, 14, 10, 10)));
+        assert!(!Circle::new(10, 10, 5).collide_rect(&Rect::new(15, 15, 10, 10)));
+        assert!(Circle::new(10, 10, 5).collide_rect(&Rect::new(0, 0, 20, 20)));
+        assert!(Circle::new(10, 10, 50).collide_rect(&Rect::new(0, 0, 2, 2)));
+        assert!(Circle::new(10, 10, 0).collide_rect(&Rect::new(5, 5, 10, 10)));
+        assert!(!Circle::new(10, 10, 0).collide_rect(&Rect::new(5, 5, 5, 5)));
+        assert!(Circle::new(10, 10, 5).collide_rect(&Rect::new(25, 10, -10, 5)));
+        assert!(!Circle::new(10, 10, 5).collide_rect(&Rect::new(26, 10, -10, 5)));
+        assert!(Circle::new(10, 10, 5).collide_rect(&Rect::new(15, 10, 0, 0)));
+    }
+}
@@ -1,3 +1,7 @@
+pub mod color_space;
+
+use std::ops::{Add, Div, Mul, Sub};
+
 pub trait Color {
     fn r(&self) -> u8;
     fn g(&self) -> u8;
@@ -15,13 +19,21 @@ pub trait Color {
     }
 }
 
+// the layout of `ColorU8` is the layout of the RGBA8 pixels of a surface, which allows `PixelView` to reinterpret the raw data
 #[derive(Debug, PartialEq, Clone, Copy)]
+#[repr(transparent)]
 pub struct ColorRGBA<T> {
     rgba: [T; 4],
 }
 
 pub type ColorU8 = ColorRGBA<u8>;
 
+/// Color with channels in the range [0, 1], for color math without the rounding of `ColorU8`.
+///
+/// The channels are not clamped, so intermediate results of the arithmetic operators can leave the range.
+/// The conversion from `ColorU8` and back is lossless.
+pub type ColorF32 = ColorRGBA<f32>;
+
 impl ColorRGBA<u8> {
     pub fn new(r: u8, g: u8, b: u8, a: u8) -> ColorU8 {
         ColorU8 { rgba: [r, g, b, a] }
@@ -47,12 +59,18 @@ impl ColorRGBA<u8> {
         }
     }
 
-    // TODO: HSL/HSV color module
-
     fn f32_to_u8(c: f32) -> u8 {
         ((c * 255.0).round() as i32).clamp(0, 255) as u8
     }
 
+    fn percent_to_f32(percent: u8) -> f32 {
+        percent.min(100) as f32 / 100.0
+    }
+
+    fn f32_to_percent(c: f32) -> u8 {
+        (c * 100.0).round().clamp(0.0, 100.0) as u8
+    }
+
     fn hue_to_rgb(h: f32) -> (f32, f32, f32) {
         (
             ((h * 6.0 - 3.0).abs() - 1.0).clamp(0.0, 1.0),
@@ -61,86 +79,124 @@ impl ColorRGBA<u8> {
         )
     }
 
-    pub fn from_hue(hue: u16) -> ColorU8 {
-        let rgb = ColorRGBA::hue_to_rgb((hue % 360) as f32 / 360.0);
+    fn from_f32_rgb(rgb: (f32, f32, f32), alpha: u8) -> ColorU8 {
         ColorU8 {
             rgba: [
                 ColorRGBA::f32_to_u8(rgb.0),
                 ColorRGBA::f32_to_u8(rgb.1),
                 ColorRGBA::f32_to_u8(rgb.2),
-                255,
+                alpha,
             ],
         }
     }
 
+    // hue in degrees, the maximum and the minimum of the color channels in the range [0, 1]
+    fn hue_max_min(&self) -> (u16, f32, f32) {
+        let [r, g, b] = [self.rgba[0], self.rgba[1], self.rgba[2]].map(|c| c as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+        let hue = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / chroma + 2.0
+        } else {
+            (r - g) / chroma + 4.0
+        };
+        ((hue * 60.0).round() as u16 % 360, max, min)
+    }
+
+    /// Fully saturated color of the hue in degrees.
+    pub fn from_hue(hue: u16) -> ColorU8 {
+        ColorU8::from_hue_alpha(hue, 255)
+    }
+
     pub fn from_hue_alpha(hue: u16, alpha: u8) -> ColorU8 {
-        let rgb = ColorRGBA::hue_to_rgb((hue % 360) as f32 / 360.0);
-        ColorU8 {
-            rgba: [
-                ColorRGBA::f32_to_u8(rgb.0),
-                ColorRGBA::f32_to_u8(rgb.1),
-                ColorRGBA::f32_to_u8(rgb.2),
-                (alpha as f32 * 255.0 / 100.0).round() as u8,
-            ],
-        }
+        ColorU8::from_f32_rgb(ColorRGBA::hue_to_rgb((hue % 360) as f32 / 360.0), alpha)
     }
 
+    /// Hue in degrees, saturation and value in percent.
     pub fn from_hsv(hue: u16, saturation: u8, value: u8) -> ColorU8 {
-        let rgb = ColorRGBA::hue_to_rgb((hue % 360) as f32 / 360.0);
-        let s = saturation as f32 / 100.0;
-        let v = value as f32 / 100.0;
-        ColorU8 {
-            rgba: [
-                ColorRGBA::f32_to_u8(((rgb.0 - 1.0) * s + 1.0) * v),
-                ColorRGBA::f32_to_u8(((rgb.1 - 1.0) * s + 1.0) * v),
-                ColorRGBA::f32_to_u8(((rgb.2 - 1.0) * s + 1.0) * v),
-                255,
-            ],
-        }
+        ColorU8::from_hsv_alpha(hue, saturation, value, 255)
     }
 
     pub fn from_hsv_alpha(hue: u16, saturation: u8, value: u8, alpha: u8) -> ColorU8 {
         let rgb = ColorRGBA::hue_to_rgb((hue % 360) as f32 / 360.0);
-        let s = saturation as f32 / 100.0;
-        let v = value as f32 / 100.0;
-        ColorU8 {
-            rgba: [
-                ColorRGBA::f32_to_u8(((rgb.0 - 1.0) * s + 1.0) * v),
-                ColorRGBA::f32_to_u8(((rgb.1 - 1.0) * s + 1.0) * v),
-                ColorRGBA::f32_to_u8(((rgb.2 - 1.0) * s + 1.0) * v),
-                (alpha as f32 * 255.0 / 100.0).round() as u8,
-            ],
-        }
+        let s = ColorRGBA::percent_to_f32(saturation);
+        let v = ColorRGBA::percent_to_f32(value);
+        ColorU8::from_f32_rgb((((rgb.0 - 1.0) * s + 1.0) * v, ((rgb.1 - 1.0) * s + 1.0) * v, ((rgb.2 - 1.0) * s + 1.0) * v), alpha)
+    }
+
+    /// Hue in degrees, saturation and value in percent, like the arguments of `from_hsv`.
+    pub fn to_hsv(&self) -> (u16, u8, u8) {
+        let (hue, max, min) = self.hue_max_min();
+        let saturation = if max > 0.0 { (max - min) / max } else { 0.0 };
+        (hue, ColorRGBA::f32_to_percent(saturation), ColorRGBA::f32_to_percent(max))
     }
 
+    /// Hue in degrees, saturation and lightness in percent.
     pub fn from_hsl(hue: u16, saturation: u8, lightness: u8) -> ColorU8 {
-        let rgb = ColorRGBA::hue_to_rgb((hue % 360) as f32 / 360.0);
-        let s = saturation as f32 / 100.0;
-        let l = lightness as f32 / 100.0;
-        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
-        ColorU8 {
-            rgba: [
-                ColorRGBA::f32_to_u8(((rgb.0 - 0.5) * c) + l),
-                ColorRGBA::f32_to_u8(((rgb.1 - 0.5) * c) + l),
-                ColorRGBA::f32_to_u8(((rgb.2 - 0.5) * c) + l),
-                255,
-            ],
-        }
+        ColorU8::from_hsl_alpha(hue, saturation, lightness, 255)
     }
 
     pub fn from_hsl_alpha(hue: u16, saturation: u8, lightness: u8, alpha: u8) -> ColorU8 {
         let rgb = ColorRGBA::hue_to_rgb((hue % 360) as f32 / 360.0);
-        let s = saturation as f32 / 100.0;
-        let l = lightness as f32 / 100.0;
+        let s = ColorRGBA::percent_to_f32(saturation);
+        let l = ColorRGBA::percent_to_f32(lightness);
         let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
-        ColorU8 {
-            rgba: [
-                ColorRGBA::f32_to_u8(((rgb.0 - 0.5) * c) + l),
-                ColorRGBA::f32_to_u8(((rgb.1 - 0.5) * c) + l),
-                ColorRGBA::f32_to_u8(((rgb.2 - 0.5) * c) + l),
-                (alpha as f32 * 255.0 / 100.0).round() as u8,
-            ],
+        ColorU8::from_f32_rgb(((rgb.0 - 0.5) * c + l, (rgb.1 - 0.5) * c + l, (rgb.2 - 0.5) * c + l), alpha)
+    }
+
+    /// Hue in degrees, saturation and lightness in percent, like the arguments of `from_hsl`.
+    pub fn to_hsl(&self) -> (u16, u8, u8) {
+        let (hue, max, min) = self.hue_max_min();
+        let lightness = (max + min) / 2.0;
+        let saturation = match lightness > 0.0 && lightness < 1.0 {
+            true => (max - min) / (1.0 - (2.0 * lightness - 1.0).abs()),
+            false => 0.0,
+        };
+        (hue, ColorRGBA::f32_to_percent(saturation), ColorRGBA::f32_to_percent(lightness))
+    }
+
+    /// Hue in degrees, whiteness and blackness in percent, a sum of whiteness and blackness of 100 or more gives a gray.
+    pub fn from_hwb(hue: u16, whiteness: u8, blackness: u8) -> ColorU8 {
+        ColorU8::from_hwb_alpha(hue, whiteness, blackness, 255)
+    }
+
+    pub fn from_hwb_alpha(hue: u16, whiteness: u8, blackness: u8, alpha: u8) -> ColorU8 {
+        let w = ColorRGBA::percent_to_f32(whiteness);
+        let b = ColorRGBA::percent_to_f32(blackness);
+        if w + b >= 1.0 {
+            let gray = w / (w + b);
+            return ColorU8::from_f32_rgb((gray, gray, gray), alpha);
         }
+        let rgb = ColorRGBA::hue_to_rgb((hue % 360) as f32 / 360.0);
+        let c = 1.0 - w - b;
+        ColorU8::from_f32_rgb((rgb.0 * c + w, rgb.1 * c + w, rgb.2 * c + w), alpha)
+    }
+
+    /// Hue in degrees, whiteness and blackness in percent, like the arguments of `from_hwb`.
+    pub fn to_hwb(&self) -> (u16, u8, u8) {
+        let (hue, max, min) = self.hue_max_min();
+        (hue, ColorRGBA::f32_to_percent(min), ColorRGBA::f32_to_percent(1.0 - max))
+    }
+
+    /// Cyan, magenta and yellow in percent.
+    pub fn from_cmy(cyan: u8, magenta: u8, yellow: u8) -> ColorU8 {
+        ColorU8::from_cmy_alpha(cyan, magenta, yellow, 255)
+    }
+
+    pub fn from_cmy_alpha(cyan: u8, magenta: u8, yellow: u8, alpha: u8) -> ColorU8 {
+        let [r, g, b] = [cyan, magenta, yellow].map(|c| 1.0 - ColorRGBA::percent_to_f32(c));
+        ColorU8::from_f32_rgb((r, g, b), alpha)
+    }
+
+    /// Cyan, magenta and yellow in percent, like the arguments of `from_cmy`.
+    pub fn to_cmy(&self) -> (u8, u8, u8) {
+        let [c, m, y] = [self.rgba[0], self.rgba[1], self.rgba[2]].map(|c| ColorRGBA::f32_to_percent(1.0 - c as f32 / 255.0));
+        (c, m, y)
     }
 }
 
@@ -171,6 +227,189 @@ impl Color for ColorRGBA<u8> {
     }
 }
 
+impl ColorRGBA<f32> {
+    pub fn new_rgba(r: f32, g: f32, b: f32, a: f32) -> ColorF32 {
+        ColorF32 { rgba: [r, g, b, a] }
+    }
+
+    pub fn new_rgb(r: f32, g: f32, b: f32) -> ColorF32 {
+        ColorF32 { rgba: [r, g, b, 1.0] }
+    }
+
+    pub fn new_gray(grayscale: f32) -> ColorF32 {
+        ColorF32 {
+            rgba: [grayscale, grayscale, grayscale, 1.0],
+        }
+    }
+
+    pub fn new_gray_alpha(grayscale: f32, alpha: f32) -> ColorF32 {
+        ColorF32 {
+            rgba: [grayscale, grayscale, grayscale, alpha],
+        }
+    }
+
+    pub fn from_color(color: &dyn Color) -> ColorF32 {
+        ColorF32 {
+            rgba: [color.r(), color.g(), color.b(), color.a()].map(|c| c as f32 / 255.0),
+        }
+    }
+
+    pub fn get_r(&self) -> f32 {
+        self.rgba[0]
+    }
+
+    pub fn get_g(&self) -> f32 {
+        self.rgba[1]
+    }
+
+    pub fn get_b(&self) -> f32 {
+        self.rgba[2]
+    }
+
+    pub fn get_a(&self) -> f32 {
+        self.rgba[3]
+    }
+
+    pub fn get_rgba(&self) -> [f32; 4] {
+        self.rgba
+    }
+
+    fn map_rgb<F: Fn(f32) -> f32>(&self, f: F) -> ColorF32 {
+        ColorF32 {
+            rgba: [f(self.rgba[0]), f(self.rgba[1]), f(self.rgba[2]), self.rgba[3]],
+        }
+    }
+
+    pub fn clamp(&self) -> ColorF32 {
+        ColorF32 {
+            rgba: self.rgba.map(|c| c.clamp(0.0, 1.0)),
+        }
+    }
+
+    /// Linear interpolation of all 4 channels, `t` = 0 gives `self` and `t` = 1 gives `other`.
+    pub fn lerp(&self, other: &ColorF32, t: f32) -> ColorF32 {
+        *self + (*other - *self) * t
+    }
+
+    /// Multiplies the color channels by alpha.
+    pub fn premultiply(&self) -> ColorF32 {
+        self.map_rgb(|c| c * self.rgba[3])
+    }
+
+    /// Divides the color channels by alpha, a transparent color becomes transparent black.
+    pub fn unpremultiply(&self) -> ColorF32 {
+        match self.rgba[3] > 0.0 {
+            true => self.map_rgb(|c| c / self.rgba[3]),
+            false => ColorF32 { rgba: [0.0; 4] },
+        }
+    }
+
+    /// Converts the color channels from the sRGB transfer function to linear light, alpha is kept.
+    pub fn srgb_to_linear(&self) -> ColorF32 {
+        self.map_rgb(|c| match c <= 0.04045 {
+            true => c / 12.92,
+            false => ((c + 0.055) / 1.055).powf(2.4),
+        })
+    }
+
+    /// Converts the color channels from linear light to the sRGB transfer function, alpha is kept.
+    pub fn linear_to_srgb(&self) -> ColorF32 {
+        self.map_rgb(|c| match c <= 0.0031308 {
+            true => c * 12.92,
+            false => 1.055 * c.powf(1.0 / 2.4) - 0.055,
+        })
+    }
+}
+
+impl Color for ColorRGBA<f32> {
+    fn r(&self) -> u8 {
+        ColorU8::f32_to_u8(self.rgba[0])
+    }
+    fn g(&self) -> u8 {
+        ColorU8::f32_to_u8(self.rgba[1])
+    }
+    fn b(&self) -> u8 {
+        ColorU8::f32_to_u8(self.rgba[2])
+    }
+    fn a(&self) -> u8 {
+        ColorU8::f32_to_u8(self.rgba[3])
+    }
+    fn set_r(&mut self, r: u8) {
+        self.rgba[0] = r as f32 / 255.0;
+    }
+    fn set_g(&mut self, g: u8) {
+        self.rgba[1] = g as f32 / 255.0;
+    }
+    fn set_b(&mut self, b: u8) {
+        self.rgba[2] = b as f32 / 255.0;
+    }
+    fn set_a(&mut self, a: u8) {
+        self.rgba[3] = a as f32 / 255.0;
+    }
+}
+
+impl From<ColorU8> for ColorF32 {
+    fn from(color: ColorU8) -> ColorF32 {
+        ColorF32::from_color(&color)
+    }
+}
+
+impl From<ColorF32> for ColorU8 {
+    fn from(color: ColorF32) -> ColorU8 {
+        ColorU8::new_rgba(color.r(), color.g(), color.b(), color.a())
+    }
+}
+
+impl Add for ColorF32 {
+    type Output = ColorF32;
+
+    fn add(self, other: ColorF32) -> ColorF32 {
+        ColorF32 {
+            rgba: std::array::from_fn(|i| self.rgba[i] + other.rgba[i]),
+        }
+    }
+}
+
+impl Sub for ColorF32 {
+    type Output = ColorF32;
+
+    fn sub(self, other: ColorF32) -> ColorF32 {
+        ColorF32 {
+            rgba: std::array::from_fn(|i| self.rgba[i] - other.rgba[i]),
+        }
+    }
+}
+
+impl Mul for ColorF32 {
+    type Output = ColorF32;
+
+    fn mul(self, other: ColorF32) -> ColorF32 {
+        ColorF32 {
+            rgba: std::array::from_fn(|i| self.rgba[i] * other.rgba[i]),
+        }
+    }
+}
+
+impl Mul<f32> for ColorF32 {
+    type Output = ColorF32;
+
+    fn mul(self, factor: f32) -> ColorF32 {
+        ColorF32 {
+            rgba: self.rgba.map(|c| c * factor),
+        }
+    }
+}
+
+impl Div<f32> for ColorF32 {
+    type Output = ColorF32;
+
+    fn div(self, divisor: f32) -> ColorF32 {
+        ColorF32 {
+            rgba: self.rgba.map(|c| c / divisor),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test_color_u8 {
     use super::*;
@@ -227,7 +466,7 @@ mod test_color_u8 {
 
     #[test]
     fn color_from_hue_alpha() {
-        let color = ColorU8::from_hue_alpha(180, 50);
+        let color = ColorU8::from_hue_alpha(180, 128);
         assert_eq!(ColorU8::new(0, 255, 255, 128), color);
     }
 
@@ -239,7 +478,7 @@ mod test_color_u8 {
 
     #[test]
     fn color_from_hsv_alpha() {
-        let color = ColorU8::from_hsv_alpha(180, 100, 50, 50);
+        let color = ColorU8::from_hsv_alpha(180, 100, 50, 128);
         assert_eq!(ColorU8::new(0, 128, 128, 128), color);
     }
 
@@ -251,7 +490,154 @@ mod test_color_u8 {
 
     #[test]
     fn color_from_hsl_alpha() {
-        let color = ColorU8::from_hsl_alpha(180, 50, 50, 50);
+        let color = ColorU8::from_hsl_alpha(180, 50, 50, 128);
         assert_eq!(ColorU8::new(64, 191, 191, 128), color);
     }
+
+    #[test]
+    fn color_to_hsv() {
+        assert_eq!((180, 100, 50), ColorU8::new(0, 128, 128, 255).to_hsv());
+        assert_eq!((0, 0, 50), ColorU8::new_gray(128).to_hsv());
+        assert_eq!((0, 0, 0), ColorU8::new_gray(0).to_hsv());
+        for hue in 0..360 {
+            assert_eq!((hue, 100, 100), ColorU8::from_hsv(hue, 100, 100).to_hsv());
+            assert_eq!((hue, 50, 80), ColorU8::from_hsv(hue, 50, 80).to_hsv());
+        }
+    }
+
+    #[test]
+    fn color_to_hsl() {
+        assert_eq!((180, 50, 50), ColorU8::new(64, 191, 191, 255).to_hsl());
+        assert_eq!((0, 0, 100), ColorU8::new_gray(255).to_hsl());
+        for hue in 0..360 {
+            assert_eq!((hue, 100, 50), ColorU8::from_hsl(hue, 100, 50).to_hsl());
+            assert_eq!((hue, 60, 60), ColorU8::from_hsl(hue, 60, 60).to_hsl());
+        }
+    }
+
+    #[test]
+    fn color_from_hwb() {
+        assert_eq!(ColorU8::new(0, 255, 255, 255), ColorU8::from_hwb(180, 0, 0));
+        assert_eq!(ColorU8::new(51, 153, 153, 128), ColorU8::from_hwb_alpha(180, 20, 40, 128));
+        assert_eq!(ColorU8::new_gray(85), ColorU8::from_hwb(90, 40, 80));
+    }
+
+    #[test]
+    fn color_to_hwb() {
+        assert_eq!((180, 20, 40), ColorU8::new(51, 153, 153, 255).to_hwb());
+        assert_eq!((0, 100, 0), ColorU8::new_gray(255).to_hwb());
+        for hue in 0..360 {
+            assert_eq!((hue, 0, 0), ColorU8::from_hwb(hue, 0, 0).to_hwb());
+            assert_eq!((hue, 10, 20), ColorU8::from_hwb(hue, 10, 20).to_hwb());
+        }
+    }
+
+    #[test]
+    fn color_from_cmy() {
+        assert_eq!(ColorU8::new(255, 0, 128, 255), ColorU8::from_cmy(0, 100, 50));
+        assert_eq!(ColorU8::new(255, 0, 128, 64), ColorU8::from_cmy_alpha(0, 100, 50, 64));
+    }
+
+    #[test]
+    fn color_to_cmy() {
+        assert_eq!((0, 100, 50), ColorU8::new(255, 0, 128, 255).to_cmy());
+        for value in 0..=100 {
+            assert_eq!((value, 100 - value, value / 2), ColorU8::from_cmy(value, 100 - value, value / 2).to_cmy());
+        }
+        for hue in (0..360).step_by(30) {
+            let color = ColorU8::from_hue(hue);
+            let (c, m, y) = color.to_cmy();
+            assert_eq!(color, ColorU8::from_cmy(c, m, y));
+        }
+    }
+
+    #[test]
+    fn color_alpha_range() {
+        assert_eq!(64, ColorU8::from_hue_alpha(0, 64).a());
+        assert_eq!(64, ColorU8::from_hsv_alpha(0, 100, 100, 64).a());
+        assert_eq!(64, ColorU8::from_hsl_alpha(0, 100, 50, 64).a());
+        assert_eq!(ColorU8::new_rgba(255, 0, 0, 64), ColorU8::from_hwb_alpha(0, 0, 0, 64));
+    }
+}
+
+#[cfg(test)]
+mod test_color_f32 {
+    use super::*;
+
+    fn assert_near(expected: ColorF32, actual: ColorF32) {
+        for (e, a) in expected.get_rgba().iter().zip(actual.get_rgba()) {
+            assert!((e - a).abs() < 1e-5, "{:?} != {:?}", expected, actual);
+        }
+    }
+
+    #[test]
+    fn color_new() {
+        let color = ColorF32::new_rgba(0.25, 0.5, 0.75, 1.0);
+        assert_eq!([0.25, 0.5, 0.75, 1.0], color.get_rgba());
+        assert_eq!(64, color.r());
+        assert_eq!(128, color.g());
+        assert_eq!(191, color.b());
+        assert_eq!(255, color.a());
+        assert_eq!(ColorF32::new_rgba(0.5, 0.5, 0.5, 1.0), ColorF32::new_gray(0.5));
+        assert_eq!(ColorF32::new_rgba(0.5, 0.5, 0.5, 0.25), ColorF32::new_gray_alpha(0.5, 0.25));
+        assert_eq!(0, ColorF32::new_rgb(-1.0, 2.0, 0.0).r());
+        assert_eq!(255, ColorF32::new_rgb(-1.0, 2.0, 0.0).g());
+    }
+
+    #[test]
+    fn color_set() {
+        let mut color = ColorF32::new_gray(0.0);
+        color.set(&ColorU8::new(255, 51, 0, 102));
+        assert_near(ColorF32::new_rgba(1.0, 0.2, 0.0, 0.4), color);
+    }
+
+    #[test]
+    fn color_conversion() {
+        for value in 0..=255 {
+            let color = ColorU8::new_rgba(value, 255 - value, value / 2, value);
+            let color_f32 = ColorF32::from(color);
+            assert_eq!(value as f32 / 255.0, color_f32.get_r());
+            assert_eq!(color, ColorU8::from(color_f32));
+        }
+    }
+
+    #[test]
+    fn color_operators() {
+        let a = ColorF32::new_rgba(0.5, 0.25, 0.0, 1.0);
+        let b = ColorF32::new_rgba(0.25, 0.25, 0.5, 0.5);
+        assert_eq!(ColorF32::new_rgba(0.75, 0.5, 0.5, 1.5), a + b);
+        assert_eq!(ColorF32::new_rgba(0.25, 0.0, -0.5, 0.5), a - b);
+        assert_eq!(ColorF32::new_rgba(0.125, 0.0625, 0.0, 0.5), a * b);
+        assert_eq!(ColorF32::new_rgba(1.0, 0.5, 0.0, 2.0), a * 2.0);
+        assert_eq!(ColorF32::new_rgba(0.25, 0.125, 0.0, 0.5), a / 2.0);
+        assert_eq!(ColorF32::new_rgba(0.75, 0.5, 0.5, 1.0), (a + b).clamp());
+    }
+
+    #[test]
+    fn color_lerp() {
+        let a = ColorF32::new_rgba(0.0, 1.0, 0.5, 1.0);
+        let b = ColorF32::new_rgba(1.0, 0.0, 0.5, 0.0);
+        assert_eq!(a, a.lerp(&b, 0.0));
+        assert_eq!(b, a.lerp(&b, 1.0));
+        assert_eq!(ColorF32::new_rgba(0.25, 0.75, 0.5, 0.75), a.lerp(&b, 0.25));
+    }
+
+    #[test]
+    fn color_premultiply() {
+        let color = ColorF32::new_rgba(1.0, 0.5, 0.25, 0.5);
+        assert_eq!(ColorF32::new_rgba(0.5, 0.25, 0.125, 0.5), color.premultiply());
+        assert_eq!(color, color.premultiply().unpremultiply());
+        assert_eq!(ColorF32::new_rgba(0.0, 0.0, 0.0, 0.0), ColorF32::new_rgba(1.0, 1.0, 1.0, 0.0).unpremultiply());
+    }
+
+    #[test]
+    fn color_srgb_to_linear() {
+        let color = ColorF32::new_rgba(0.0, 0.5, 1.0, 0.5);
+        assert_near(ColorF32::new_rgba(0.0, 0.214_041_14, 1.0, 0.5), color.srgb_to_linear());
+        assert_near(ColorF32::new_rgb(0.04 / 12.92, 0.01 / 12.92, 0.0), ColorF32::new_rgb(0.04, 0.01, 0.0).srgb_to_linear());
+        for value in 0..=255 {
+            let color = ColorF32::from(ColorU8::new_gray(value));
+            assert_eq!(ColorU8::new_gray(value), ColorU8::from(color.srgb_to_linear().linear_to_srgb()));
+        }
+    }
 }
@@ -0,0 +1,293 @@
+//! Conversions of `ColorU8` to and from the CIE XYZ, CIE Lab, CIE LCh, OKLab and OKLCh color spaces.
+//!
+//! The colors are sRGB colors with the D65 white point. XYZ has the range [0, 1] for Y, Lab and LCh the range [0, 100] for L
+//! and OKLab and OKLCh the range [0, 1] for L. Hues are in degrees. Colors outside of the sRGB gamut are clamped by the `from_*` functions.
+
+use crate::color::{Color, ColorF32, ColorU8};
+
+/// Color space in which `mix_in` interpolates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorSpace {
+    Srgb,
+    LinearSrgb,
+    Xyz,
+    Lab,
+    Lch,
+    OkLab,
+    OkLch,
+}
+
+const WHITE_D65: (f32, f32, f32) = (0.950_47, 1.0, 1.088_83);
+const LAB_DELTA: f32 = 6.0 / 29.0;
+
+fn lab_f(t: f32) -> f32 {
+    match t > LAB_DELTA.powi(3) {
+        true => t.cbrt(),
+        false => t / (3.0 * LAB_DELTA * LAB_DELTA) + 4.0 / 29.0,
+    }
+}
+
+fn lab_f_inverse(t: f32) -> f32 {
+    match t > LAB_DELTA {
+        true => t.powi(3),
+        false => 3.0 * LAB_DELTA * LAB_DELTA * (t - 4.0 / 29.0),
+    }
+}
+
+// chroma and hue in degrees of the opponent color axes
+fn to_polar(a: f32, b: f32) -> (f32, f32) {
+    (a.hypot(b), b.atan2(a).to_degrees().rem_euclid(360.0))
+}
+
+fn from_polar(chroma: f32, hue: f32) -> (f32, f32) {
+    let (sin, cos) = hue.to_radians().sin_cos();
+    (chroma * cos, chroma * sin)
+}
+
+fn to_linear_rgb(color: &ColorU8) -> (f32, f32, f32) {
+    let [r, g, b, _] = ColorF32::from(*color).srgb_to_linear().get_rgba();
+    (r, g, b)
+}
+
+fn from_linear_rgb(rgb: (f32, f32, f32)) -> ColorU8 {
+    ColorU8::from(ColorF32::new_rgb(rgb.0, rgb.1, rgb.2).linear_to_srgb())
+}
+
+impl ColorU8 {
+    pub fn to_xyz(&self) -> (f32, f32, f32) {
+        let (r, g, b) = to_linear_rgb(self);
+        (
+            0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+            0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b,
+            0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b,
+        )
+    }
+
+    pub fn from_xyz(x: f32, y: f32, z: f32) -> ColorU8 {
+        let rgb = (
+            3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+            -0.969_266 * x + 1.876_010_8 * y + 0.041_556 * z,
+            0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+        );
+        from_linear_rgb(rgb)
+    }
+
+    pub fn to_lab(&self) -> (f32, f32, f32) {
+        let (x, y, z) = self.to_xyz();
+        let (fx, fy, fz) = (lab_f(x / WHITE_D65.0), lab_f(y / WHITE_D65.1), lab_f(z / WHITE_D65.2));
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+
+    pub fn from_lab(l: f32, a: f32, b: f32) -> ColorU8 {
+        let fy = (l + 16.0) / 116.0;
+        let (fx, fz) = (fy + a / 500.0, fy - b / 200.0);
+        ColorU8::from_xyz(WHITE_D65.0 * lab_f_inverse(fx), WHITE_D65.1 * lab_f_inverse(fy), WHITE_D65.2 * lab_f_inverse(fz))
+    }
+
+    /// Lightness, chroma and hue in degrees of CIE Lab.
+    pub fn to_lch(&self) -> (f32, f32, f32) {
+        let (l, a, b) = self.to_lab();
+        let (chroma, hue) = to_polar(a, b);
+        (l, chroma, hue)
+    }
+
+    pub fn from_lch(l: f32, chroma: f32, hue: f32) -> ColorU8 {
+        let (a, b) = from_polar(chroma, hue);
+        ColorU8::from_lab(l, a, b)
+    }
+
+    pub fn to_oklab(&self) -> (f32, f32, f32) {
+        let (r, g, b) = to_linear_rgb(self);
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        (
+            0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        )
+    }
+
+    pub fn from_oklab(l: f32, a: f32, b: f32) -> ColorU8 {
+        let l_ = (l + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+        let m_ = (l - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+        let s_ = (l - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+        let rgb = (
+            4.076_741_7 * l_ - 3.307_711_6 * m_ + 0.230_969_94 * s_,
+            -1.268_438 * l_ + 2.609_757_4 * m_ - 0.341_319_38 * s_,
+            -0.004_196_086_3 * l_ - 0.703_418_6 * m_ + 1.707_614_7 * s_,
+        );
+        from_linear_rgb(rgb)
+    }
+
+    /// Lightness, chroma and hue in degrees of OKLab.
+    pub fn to_oklch(&self) -> (f32, f32, f32) {
+        let (l, a, b) = self.to_oklab();
+        let (chroma, hue) = to_polar(a, b);
+        (l, chroma, hue)
+    }
+
+    pub fn from_oklch(l: f32, chroma: f32, hue: f32) -> ColorU8 {
+        let (a, b) = from_polar(chroma, hue);
+        ColorU8::from_oklab(l, a, b)
+    }
+}
+
+fn to_space(space: ColorSpace, color: &ColorU8) -> (f32, f32, f32) {
+    match space {
+        ColorSpace::Srgb => {
+            let [r, g, b, _] = ColorF32::from(*color).get_rgba();
+            (r, g, b)
+        }
+        ColorSpace::LinearSrgb => to_linear_rgb(color),
+        ColorSpace::Xyz => color.to_xyz(),
+        ColorSpace::Lab => color.to_lab(),
+        ColorSpace::Lch => color.to_lch(),
+        ColorSpace::OkLab => color.to_oklab(),
+        ColorSpace::OkLch => color.to_oklch(),
+    }
+}
+
+fn from_space(space: ColorSpace, c: (f32, f32, f32)) -> ColorU8 {
+    match space {
+        ColorSpace::Srgb => ColorU8::from(ColorF32::new_rgb(c.0, c.1, c.2)),
+        ColorSpace::LinearSrgb => from_linear_rgb(c),
+        ColorSpace::Xyz => ColorU8::from_xyz(c.0, c.1, c.2),
+        ColorSpace::Lab => ColorU8::from_lab(c.0, c.1, c.2),
+        ColorSpace::Lch => ColorU8::from_lch(c.0, c.1, c.2),
+        ColorSpace::OkLab => ColorU8::from_oklab(c.0, c.1, c.2),
+        ColorSpace::OkLch => ColorU8::from_oklch(c.0, c.1, c.2),
+    }
+}
+
+// interpolates the hue along the shorter arc, the hue of a gray is replaced by the hue of the other color
+fn mix_hue(a: (f32, f32), b: (f32, f32), t: f32) -> f32 {
+    const GRAY_CHROMA: f32 = 1e-4;
+    let (hue_a, hue_b) = match (a.0 < GRAY_CHROMA, b.0 < GRAY_CHROMA) {
+        (true, false) => (b.1, b.1),
+        (false, true) => (a.1, a.1),
+        _ => (a.1, b.1),
+    };
+    let difference = (hue_b - hue_a + 180.0).rem_euclid(360.0) - 180.0;
+    (hue_a + difference * t).rem_euclid(360.0)
+}
+
+/// Interpolates the colors in the color space, `t` = 0 gives `a` and `t` = 1 gives `b`, alpha is interpolated linearly.
+///
+/// In `Lch` and `OkLch` the hue is interpolated along the shorter arc of the hue circle.
+pub fn mix_in(space: ColorSpace, a: &ColorU8, b: &ColorU8, t: f32) -> ColorU8 {
+    let (ca, cb) = (to_space(space, a), to_space(space, b));
+    let lerp = |x: f32, y: f32| x + (y - x) * t;
+    let mixed = match space {
+        ColorSpace::Lch | ColorSpace::OkLch => (lerp(ca.0, cb.0), lerp(ca.1, cb.1), mix_hue((ca.1, ca.2), (cb.1, cb.2), t)),
+        _ => (lerp(ca.0, cb.0), lerp(ca.1, cb.1), lerp(ca.2, cb.2)),
+    };
+    let mut color = from_space(space, mixed);
+    color.set_a((lerp(a.a() as f32, b.a() as f32)).round().clamp(0.0, 255.0) as u8);
+    color
+}
+
+/// CIE76 color difference, the euclidean distance in CIE Lab. A difference of about 2.3 is just noticeable.
+pub fn delta_e(a: &ColorU8, b: &ColorU8) -> f32 {
+    let (lab_a, lab_b) = (a.to_lab(), b.to_lab());
+    ((lab_a.0 - lab_b.0).powi(2) + (lab_a.1 - lab_b.1).powi(2) + (lab_a.2 - lab_b.2).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod color_space_test {
+    use super::*;
+
+    fn assert_near(expected: (f32, f32, f32), actual: (f32, f32, f32), tolerance: f32) {
+        let difference = [expected.0 - actual.0, expected.1 - actual.1, expected.2 - actual.2];
+        assert!(difference.iter().all(|d| d.abs() < tolerance), "{:?} != {:?}", expected, actual);
+    }
+
+    fn red() -> ColorU8 {
+        ColorU8::new_rgb(255, 0, 0)
+    }
+
+    #[test]
+    fn xyz_test() {
+        assert_near((0.950_47, 1.0, 1.088_83), ColorU8::new_gray(255).to_xyz(), 1e-3);
+        assert_near((0.412_456, 0.212_673, 0.019_334), red().to_xyz(), 1e-5);
+        assert_eq!(red(), ColorU8::from_xyz(0.412_456, 0.212_673, 0.019_334));
+    }
+
+    #[test]
+    fn lab_test() {
+        assert_near((100.0, 0.0, 0.0), ColorU8::new_gray(255).to_lab(), 1e-2);
+        assert_near((0.0, 0.0, 0.0), ColorU8::new_gray(0).to_lab(), 1e-4);
+        assert_near((53.24, 80.09, 67.20), red().to_lab(), 1e-2);
+        assert_near((53.24, 104.55, 40.0), red().to_lch(), 1e-2);
+        assert_eq!(red(), ColorU8::from_lab(53.24, 80.09, 67.20));
+        assert_eq!(red(), ColorU8::from_lch(53.24, 104.55, 40.0));
+    }
+
+    #[test]
+    fn oklab_test() {
+        assert_near((1.0, 0.0, 0.0), ColorU8::new_gray(255).to_oklab(), 1e-3);
+        assert_near((0.627_96, 0.224_86, 0.125_85), red().to_oklab(), 1e-4);
+        assert_near((0.627_96, 0.257_68, 29.23), red().to_oklch(), 1e-2);
+        assert_eq!(red(), ColorU8::from_oklab(0.627_96, 0.224_86, 0.125_85));
+        assert_eq!(red(), ColorU8::from_oklch(0.627_96, 0.257_68, 29.23));
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for hue in 0..360 {
+            for color in [ColorU8::from_hue(hue), ColorU8::from_hsl(hue, 40, 30)] {
+                let (x, y, z) = color.to_xyz();
+                assert_eq!(color, ColorU8::from_xyz(x, y, z));
+                let (l, a, b) = color.to_lab();
+                assert_eq!(color, ColorU8::from_lab(l, a, b));
+                let (l, c, h) = color.to_lch();
+                assert_eq!(color, ColorU8::from_lch(l, c, h));
+                let (l, a, b) = color.to_oklab();
+                assert_eq!(color, ColorU8::from_oklab(l, a, b));
+                let (l, c, h) = color.to_oklch();
+                assert_eq!(color, ColorU8::from_oklch(l, c, h));
+            }
+        }
+    }
+
+    #[test]
+    fn mix_in_test() {
+        let (black, white) = (ColorU8::new_gray(0), ColorU8::new_gray(255));
+        assert_eq!(ColorU8::new_gray(128), mix_in(ColorSpace::Srgb, &black, &white, 0.5));
+        assert_eq!(ColorU8::new_gray(188), mix_in(ColorSpace::LinearSrgb, &black, &white, 0.5));
+        assert_eq!(ColorU8::new_gray(119), mix_in(ColorSpace::Lab, &black, &white, 0.5));
+        assert_eq!(ColorU8::new_gray(99), mix_in(ColorSpace::OkLab, &black, &white, 0.5));
+        for space in [
+            ColorSpace::Srgb,
+            ColorSpace::Xyz,
+            ColorSpace::Lab,
+            ColorSpace::Lch,
+            ColorSpace::OkLab,
+            ColorSpace::OkLch,
+        ] {
+            assert_eq!(red(), mix_in(space, &red(), &white, 0.0), "{:?}", space);
+            assert_eq!(white, mix_in(space, &red(), &white, 1.0), "{:?}", space);
+        }
+        let transparent = ColorU8::new_rgba(0, 0, 255, 0);
+        assert_eq!(128, mix_in(ColorSpace::OkLab, &red(), &transparent, 0.5).a());
+    }
+
+    #[test]
+    fn mix_in_hue_test() {
+        let (red_hue, blue_hue) = (red().to_oklch().2, ColorU8::new_rgb(0, 0, 255).to_oklch().2);
+        let purple = mix_in(ColorSpace::OkLch, &red(), &ColorU8::new_rgb(0, 0, 255), 0.5);
+        let hue = purple.to_oklch().2;
+        assert!(hue > blue_hue || hue < red_hue, "{} {} {}", red_hue, blue_hue, hue);
+        assert!(purple.r() > 100 && purple.b() > 100 && purple.g() < 100, "{:?}", purple);
+        let gray_mix = mix_in(ColorSpace::Lch, &ColorU8::new_gray(128), &red(), 0.5);
+        assert!(gray_mix.r() > gray_mix.g() && gray_mix.r() > gray_mix.b(), "{:?}", gray_mix);
+    }
+
+    #[test]
+    fn delta_e_test() {
+        assert_eq!(0.0, delta_e(&red(), &red()));
+        assert!((delta_e(&ColorU8::new_gray(0), &ColorU8::new_gray(255)) - 100.0).abs() < 1e-2);
+        assert!(delta_e(&ColorU8::new_gray(100), &ColorU8::new_gray(101)) < 1.0);
+        assert_eq!(delta_e(&red(), &ColorU8::new_rgb(0, 255, 0)), delta_e(&ColorU8::new_rgb(0, 255, 0), &red()));
+    }
+}
@@ -1,19 +1,21 @@
 use crate::canvas::Canvas;
 use crate::draw::Draw;
+use crate::error::GameError;
 use crate::events::Events;
 use crate::image::Image;
 use crate::surface::Surface;
 use crate::time::Time;
-use std::error::Error;
+use crate::transform::Transform;
 use std::time::Instant;
 
 pub trait Context {
-    fn new_canvas(&self) -> Result<Box<dyn Canvas>, Box<dyn Error>>;
-    fn events(&self) -> Result<Box<dyn Events>, Box<dyn Error>>;
-    fn time(&self) -> Result<Box<dyn Time>, Box<dyn Error>>;
-    fn new_surface_alpha_from_size(&self, size: (u32, u32)) -> Result<Box<dyn Surface>, Box<dyn Error>>;
-    fn draw(&self) -> Result<Box<dyn Draw>, Box<dyn Error>>;
-    fn image(&self) -> Result<Box<dyn Image>, Box<dyn Error>>;
+    fn new_canvas(&self) -> Result<Box<dyn Canvas>, GameError>;
+    fn events(&self) -> Result<Box<dyn Events>, GameError>;
+    fn time(&self) -> Result<Box<dyn Time>, GameError>;
+    fn new_surface_alpha_from_size(&self, size: (u32, u32)) -> Result<Box<dyn Surface>, GameError>;
+    fn draw(&self) -> Result<Box<dyn Draw>, GameError>;
+    fn image(&self) -> Result<Box<dyn Image>, GameError>;
+    fn transform(&self) -> Result<Box<dyn Transform>, GameError>;
 }
 
 pub struct ContextData {
@@ -35,23 +37,26 @@ mod context_test {
     struct ContextMock {}
 
     impl Context for ContextMock {
-        fn new_canvas(&self) -> Result<Box<dyn Canvas>, Box<dyn Error>> {
-            Err("test")?
+        fn new_canvas(&self) -> Result<Box<dyn Canvas>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
-        fn events(&self) -> Result<Box<dyn Events>, Box<dyn Error>> {
-            Err("test")?
+        fn events(&self) -> Result<Box<dyn Events>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
-        fn time(&self) -> Result<Box<dyn Time>, Box<dyn Error>> {
-            Err("test")?
+        fn time(&self) -> Result<Box<dyn Time>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
-        fn new_surface_alpha_from_size(&self, _: (u32, u32)) -> Result<Box<dyn Surface>, Box<dyn Error>> {
-            Err("test")?
+        fn new_surface_alpha_from_size(&self, _: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
-        fn draw(&self) -> Result<Box<dyn Draw>, Box<dyn Error>> {
-            Err("test")?
+        fn draw(&self) -> Result<Box<dyn Draw>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
-        fn image(&self) -> Result<Box<dyn Image>, Box<dyn Error>> {
-            Err("test")?
+        fn image(&self) -> Result<Box<dyn Image>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
+        }
+        fn transform(&self) -> Result<Box<dyn Transform>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
     }
 }
@@ -1,8 +1,10 @@
+pub mod software_draw;
+
 use crate::color::Color;
+use crate::error::GameError;
 use crate::rectangle::Rect;
 use crate::surface::Surface;
 pub use crate::utility::draw_utility::DrawUtility;
-use std::error::Error;
 use std::ops::Range;
 
 pub trait Draw {
@@ -13,7 +15,7 @@ pub trait Draw {
         color: &dyn Color,
         rectangle: Rect,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>>;
+    ) -> Result<Rect, GameError>;
 
     fn circle(
         &self,
@@ -23,7 +25,7 @@ pub trait Draw {
         center: (i32, i32),
         radius: i32,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>>;
+    ) -> Result<Rect, GameError>;
 
     fn ellipse(
         &self,
@@ -34,7 +36,7 @@ pub trait Draw {
         size: (i32, i32),
         angle: f32,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>>;
+    ) -> Result<Rect, GameError>;
 
     fn arc(
         &self,
@@ -45,7 +47,7 @@ pub trait Draw {
         radius: i32,
         arc_angle: Range<f32>,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>>;
+    ) -> Result<Rect, GameError>;
 
     fn elliptical_arc(
         &self,
@@ -57,7 +59,7 @@ pub trait Draw {
         angle: f32,
         arc_angle: Range<f32>,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>>;
+    ) -> Result<Rect, GameError>;
 
     fn polygon(
         &self,
@@ -66,7 +68,7 @@ pub trait Draw {
         color: &dyn Color,
         points: &Vec<(i32, i32)>,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>>;
+    ) -> Result<Rect, GameError>;
 
     fn line(
         &self,
@@ -76,7 +78,7 @@ pub trait Draw {
         start: (i32, i32),
         end: (i32, i32),
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>>;
+    ) -> Result<Rect, GameError>;
 
     fn lines(
         &self,
@@ -86,7 +88,7 @@ pub trait Draw {
         close: bool,
         points: &Vec<(i32, i32)>,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>>;
+    ) -> Result<Rect, GameError>;
 }
 
 #[cfg(test)]
@@ -96,12 +98,12 @@ mod test_surface {
     struct DrawMock {}
 
     impl Draw for DrawMock {
-        fn rectangle(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: Rect, _: i32) -> Result<Rect, Box<dyn Error>> {
-            Err("test")?
+        fn rectangle(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: Rect, _: i32) -> Result<Rect, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
-        fn circle(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: (i32, i32), _: i32, _: i32) -> Result<Rect, Box<dyn Error>> {
-            Err("test")?
+        fn circle(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: (i32, i32), _: i32, _: i32) -> Result<Rect, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
         fn ellipse(
@@ -113,8 +115,8 @@ mod test_surface {
             _: (i32, i32),
             _: f32,
             _: i32,
-        ) -> Result<Rect, Box<dyn Error>> {
-            Err("test")?
+        ) -> Result<Rect, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
         fn arc(
@@ -126,8 +128,8 @@ mod test_surface {
             _: i32,
             _: Range<f32>,
             _: i32,
-        ) -> Result<Rect, Box<dyn Error>> {
-            Err("test")?
+        ) -> Result<Rect, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
         fn elliptical_arc(
@@ -140,20 +142,20 @@ mod test_surface {
             _: f32,
             _: Range<f32>,
             _: i32,
-        ) -> Result<Rect, Box<dyn Error>> {
-            Err("test")?
+        ) -> Result<Rect, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
-        fn polygon(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: &Vec<(i32, i32)>, _: i32) -> Result<Rect, Box<dyn Error>> {
-            Err("test")?
+        fn polygon(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: &Vec<(i32, i32)>, _: i32) -> Result<Rect, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
-        fn line(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: (i32, i32), _: (i32, i32), _: i32) -> Result<Rect, Box<dyn Error>> {
-            Err("test")?
+        fn line(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: (i32, i32), _: (i32, i32), _: i32) -> Result<Rect, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
-        fn lines(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: bool, _: &Vec<(i32, i32)>, _: i32) -> Result<Rect, Box<dyn Error>> {
-            Err("test")?
+        fn lines(&self, _: &mut dyn Surface, _: bool, _: &dyn Color, _: bool, _: &Vec<(i32, i32)>, _: i32) -> Result<Rect, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
     }
 }
@@ -0,0 +1,668 @@
+use crate::color::Color;
+use crate::draw::Draw;
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::surface::Surface;
+use std::ops::Range;
+
+/// `Draw` implementation which rasterizes the primitives in Rust on the raw RGBA8 data of any `Surface`.
+///
+/// Integer coordinates address pixel centers and a negative `line_width` fills the shape, like in the OpenCV backend.
+pub struct SoftwareDraw {}
+
+impl SoftwareDraw {
+    pub fn new() -> Result<Box<dyn Draw>, GameError> {
+        Ok(Box::new(SoftwareDraw {}))
+    }
+}
+
+struct Raster<'a> {
+    data: &'a mut [u8],
+    pitch: usize,
+    clip: Rect,
+    color: [u8; 4],
+    antialias: bool,
+}
+
+impl<'a> Raster<'a> {
+    fn new(surface: &'a mut dyn Surface, antialias: bool, color: &dyn Color) -> Result<Raster<'a>, GameError> {
+        let (clip, pitch) = (surface.get_clip(), surface.get_pitch());
+        Ok(Raster {
+            data: surface.raw_mut()?,
+            pitch,
+            clip,
+            color: [color.r(), color.g(), color.b(), color.a()],
+            antialias,
+        })
+    }
+
+    fn plot(&mut self, x: i32, y: i32, coverage: f32) {
+        if x < self.clip.get_left() || y < self.clip.get_top() || x >= self.clip.get_right() || y >= self.clip.get_bottom() {
+            return;
+        }
+        let coverage = if self.antialias {
+            coverage.clamp(0.0, 1.0)
+        } else if coverage > 0.5 {
+            1.0
+        } else {
+            0.0
+        };
+        if coverage <= 0.0 {
+            return;
+        }
+        let i = y as usize * self.pitch + x as usize * 4;
+        if coverage >= 1.0 {
+            self.data[i..i + 4].copy_from_slice(&self.color);
+        } else {
+            for (dest, source) in self.data[i..i + 4].iter_mut().zip(self.color.iter()) {
+                *dest = (*dest as f32 + (*source as f32 - *dest as f32) * coverage).round() as u8;
+            }
+        }
+    }
+
+    fn bounds(&self, points: &[(f32, f32)], margin: f32) -> Option<(i32, i32, i32, i32)> {
+        let min_x = points.iter().map(|p| p.0).fold(f32::MAX, f32::min) - margin;
+        let max_x = points.iter().map(|p| p.0).fold(f32::MIN, f32::max) + margin;
+        let min_y = points.iter().map(|p| p.1).fold(f32::MAX, f32::min) - margin;
+        let max_y = points.iter().map(|p| p.1).fold(f32::MIN, f32::max) + margin;
+        let x0 = (min_x.floor() as i32).max(self.clip.get_left());
+        let x1 = (max_x.ceil() as i32).min(self.clip.get_right() - 1);
+        let y0 = (min_y.floor() as i32).max(self.clip.get_top());
+        let y1 = (max_y.ceil() as i32).min(self.clip.get_bottom() - 1);
+        if x0 > x1 || y0 > y1 {
+            None
+        } else {
+            Some((x0, y0, x1, y1))
+        }
+    }
+
+    fn fill_rect(&mut self, rectangle: &Rect) {
+        let x0 = rectangle.get_left().max(self.clip.get_left());
+        let x1 = rectangle.get_right().min(self.clip.get_right());
+        let y0 = rectangle.get_top().max(self.clip.get_top());
+        let y1 = rectangle.get_bottom().min(self.clip.get_bottom());
+        for y in y0..y1 {
+            for x in x0..x1 {
+                self.plot(x, y, 1.0);
+            }
+        }
+    }
+
+    fn fill_polygon(&mut self, points: &[(f32, f32)]) {
+        if let Some(bounds) = self.bounds(points, 1.0) {
+            self.scan_edges(points, true, bounds, 1.0, true, |distance| 1.0 - distance);
+        }
+    }
+
+    fn stroke_polyline(&mut self, points: &[(f32, f32)], close: bool, line_width: i32) {
+        if points.is_empty() {
+            return;
+        }
+        if !self.antialias && line_width <= 1 {
+            let count = if close { points.len() } else { points.len() - 1 };
+            for i in 0..count.max(1) {
+                let start = points[i];
+                let end = points[(i + 1) % points.len()];
+                self.line_bresenham((start.0.round() as i32, start.1.round() as i32), (end.0.round() as i32, end.1.round() as i32));
+            }
+            return;
+        }
+        let half_width = line_width.max(1) as f32 / 2.0;
+        if let Some(bounds) = self.bounds(points, half_width + 1.0) {
+            self.scan_edges(points, close, bounds, half_width + 1.0, false, |distance| half_width + 0.5 - distance);
+        }
+    }
+
+    // Rasterizes the edges row by row with an active edge list. The interior of a filled polygon is taken from the
+    // crossings of the row with the edges, the coverage from the distance is only computed within `margin` of an edge.
+    fn scan_edges<F>(&mut self, points: &[(f32, f32)], close: bool, bounds: (i32, i32, i32, i32), margin: f32, fill: bool, coverage: F)
+    where
+        F: Fn(f32) -> f32,
+    {
+        let (x0, y0, x1, y1) = bounds;
+        let count = if close || points.len() == 1 {
+            points.len()
+        } else {
+            points.len() - 1
+        };
+        let mut edges: Vec<((f32, f32), (f32, f32))> = (0..count).map(|i| (points[i], points[(i + 1) % points.len()])).collect();
+        edges.sort_by(|(a0, a1), (b0, b1)| a0.1.min(a1.1).total_cmp(&b0.1.min(b1.1)));
+        let mut active: Vec<((f32, f32), (f32, f32))> = Vec::new();
+        let mut next = 0;
+        let mut row = vec![0.0f32; (x1 - x0 + 1) as usize];
+        for y in y0..=y1 {
+            let yf = y as f32;
+            while let Some(&(a, b)) = edges.get(next).filter(|(a, b)| a.1.min(b.1) - margin <= yf) {
+                active.push((a, b));
+                next += 1;
+            }
+            active.retain(|(a, b)| a.1.max(b.1) + margin >= yf);
+            row.fill(0.0);
+            if fill {
+                let mut crossings: Vec<f32> = active
+                    .iter()
+                    .filter(|(a, b)| (a.1 > yf) != (b.1 > yf))
+                    .map(|(a, b)| (b.0 - a.0) * (yf - a.1) / (b.1 - a.1) + a.0)
+                    .collect();
+                crossings.sort_by(f32::total_cmp);
+                for pair in crossings.chunks_exact(2) {
+                    let start = (pair[0].ceil() as i32).max(x0);
+                    let end = (pair[1].ceil() as i32 - 1).min(x1);
+                    for x in start..=end {
+                        row[(x - x0) as usize] = 1.0;
+                    }
+                }
+            }
+            for &(a, b) in &active {
+                let (start, end) = match segment_x_range(a, b, yf, margin) {
+                    Some(range) => range,
+                    None => continue,
+                };
+                for x in (start.floor() as i32).max(x0)..=(end.ceil() as i32).min(x1) {
+                    let value = &mut row[(x - x0) as usize];
+                    *value = value.max(coverage(segment_distance((x as f32, yf), a, b)));
+                }
+            }
+            for (i, value) in row.iter().enumerate() {
+                if *value > 0.0 {
+                    self.plot(x0 + i as i32, y, *value);
+                }
+            }
+        }
+    }
+
+    fn line_bresenham(&mut self, start: (i32, i32), end: (i32, i32)) {
+        let (mut x, mut y) = start;
+        let dx = (end.0 - x).abs();
+        let dy = -(end.1 - y).abs();
+        let sx = if x < end.0 { 1 } else { -1 };
+        let sy = if y < end.1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        loop {
+            self.plot(x, y, 1.0);
+            if x == end.0 && y == end.1 {
+                break;
+            }
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    fn circle(&mut self, center: (i32, i32), radius: i32, line_width: i32) {
+        let c = (center.0 as f32, center.1 as f32);
+        let r = radius.max(0) as f32;
+        let half_width = line_width.max(1) as f32 / 2.0;
+        let margin = if line_width < 0 { 1.0 } else { half_width + 1.0 };
+        let (x0, y0, x1, y1) = match self.bounds(&[(c.0 - r, c.1 - r), (c.0 + r, c.1 + r)], margin) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        // pixels closer to the center than `inner` are fully covered by a filled circle and not covered by an outline
+        let (outer, inner) = if line_width < 0 {
+            (r + margin, r)
+        } else {
+            (r + margin, r - half_width - 0.5)
+        };
+        for y in y0..=y1 {
+            let dy = y as f32 - c.1;
+            if dy.abs() > outer {
+                continue;
+            }
+            let outer_half = (outer * outer - dy * dy).sqrt();
+            let inner_half = if inner > 0.0 && dy.abs() < inner {
+                (inner * inner - dy * dy).sqrt()
+            } else {
+                -1.0
+            };
+            let (inner_start, inner_end) = ((c.0 - inner_half).floor() as i32 + 1, (c.0 + inner_half).ceil() as i32 - 1);
+            let mut x = ((c.0 - outer_half).floor() as i32).max(x0);
+            let end = ((c.0 + outer_half).ceil() as i32).min(x1);
+            while x <= end {
+                if x >= inner_start && x <= inner_end {
+                    if line_width < 0 {
+                        self.plot(x, y, 1.0);
+                    } else {
+                        x = inner_end;
+                    }
+                } else {
+                    let distance = ((x as f32 - c.0).powi(2) + dy * dy).sqrt() - r;
+                    if line_width < 0 {
+                        self.plot(x, y, 1.0 - distance);
+                    } else {
+                        self.plot(x, y, half_width + 0.5 - distance.abs());
+                    }
+                }
+                x += 1;
+            }
+        }
+    }
+}
+
+fn segment_distance(p: (f32, f32), a: (f32, f32), b: (f32, f32)) -> f32 {
+    let d = (b.0 - a.0, b.1 - a.1);
+    let length_squared = d.0 * d.0 + d.1 * d.1;
+    let t = if length_squared > 0.0 {
+        (((p.0 - a.0) * d.0 + (p.1 - a.1) * d.1) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    ((a.0 + d.0 * t - p.0).powi(2) + (a.1 + d.1 * t - p.1).powi(2)).sqrt()
+}
+
+// x range of the points within `margin` of the part of the segment between the rows `y - margin` and `y + margin`
+fn segment_x_range(a: (f32, f32), b: (f32, f32), y: f32, margin: f32) -> Option<(f32, f32)> {
+    let dy = b.1 - a.1;
+    let (t0, t1) = if dy == 0.0 {
+        if (a.1 - y).abs() > margin {
+            return None;
+        }
+        (0.0, 1.0)
+    } else {
+        let (ta, tb) = ((y - margin - a.1) / dy, (y + margin - a.1) / dy);
+        (ta.min(tb).max(0.0), ta.max(tb).min(1.0))
+    };
+    if t0 > t1 {
+        return None;
+    }
+    let (xa, xb) = (a.0 + (b.0 - a.0) * t0, a.0 + (b.0 - a.0) * t1);
+    Some((xa.min(xb) - margin, xa.max(xb) + margin))
+}
+
+fn elliptical_arc_points(center: (i32, i32), size: (i32, i32), angle: f32, arc_angle: &Range<f32>) -> Vec<(f32, f32)> {
+    let (mut start, mut end) = if arc_angle.start > arc_angle.end {
+        (arc_angle.end, arc_angle.start)
+    } else {
+        (arc_angle.start, arc_angle.end)
+    };
+    if end - start >= 360.0 {
+        start = 0.0;
+        end = 360.0;
+    }
+    let (a, b) = (size.0.abs() as f32, size.1.abs() as f32);
+    let step = (90.0 / a.max(b).max(1.0)).clamp(1.0, 30.0);
+    let (sin_angle, cos_angle) = angle.to_radians().sin_cos();
+    let count = ((end - start) / step).ceil().max(1.0) as i32;
+    (0..=count)
+        .map(|i| {
+            let (sin_t, cos_t) = (start + (end - start) * i as f32 / count as f32).to_radians().sin_cos();
+            let (x, y) = (a * cos_t, b * sin_t);
+            (center.0 as f32 + x * cos_angle - y * sin_angle, center.1 as f32 + x * sin_angle + y * cos_angle)
+        })
+        .collect()
+}
+
+fn tuple_vec_to_points(points: &[(i32, i32)]) -> Vec<(f32, f32)> {
+    points.iter().map(|point| (point.0 as f32, point.1 as f32)).collect()
+}
+
+fn tuple_vec_enclosing_rectangle(points: &[(i32, i32)]) -> Rect {
+    let min_x = points.iter().map(|point| point.0).min().unwrap_or(0);
+    let max_x = points.iter().map(|point| point.0).max().unwrap_or(0);
+    let min_y = points.iter().map(|point| point.1).min().unwrap_or(0);
+    let max_y = points.iter().map(|point| point.1).max().unwrap_or(0);
+    Rect::new_from_points((min_x, min_y), (max_x, max_y))
+}
+
+impl SoftwareDraw {
+    #[allow(clippy::too_many_arguments)]
+    fn draw_elliptical_arc(
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        center: (i32, i32),
+        size: (i32, i32),
+        angle: f32,
+        arc_angle: Range<f32>,
+        line_width: i32,
+    ) -> Result<(), GameError> {
+        if arc_angle.start == arc_angle.end {
+            return Ok(());
+        }
+        let full = (arc_angle.end - arc_angle.start).abs() >= 360.0;
+        let mut points = elliptical_arc_points(center, size, angle, &arc_angle);
+        let mut raster = Raster::new(surface, antialias, color)?;
+        if line_width < 0 {
+            if !full {
+                points.push((center.0 as f32, center.1 as f32));
+            }
+            raster.fill_polygon(&points);
+        } else {
+            raster.stroke_polyline(&points, full, line_width);
+        }
+        Ok(())
+    }
+}
+
+impl Draw for SoftwareDraw {
+    fn rectangle(
+        &self,
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        rectangle: Rect,
+        line_width: i32,
+    ) -> Result<Rect, GameError> {
+        let surface_rectangle = surface.get_clip();
+        let mut raster = Raster::new(surface, antialias, color)?;
+        if line_width < 0 {
+            raster.fill_rect(&rectangle);
+        } else {
+            let (left, top) = (rectangle.get_left() as f32, rectangle.get_top() as f32);
+            let (right, bottom) = ((rectangle.get_right() - 1) as f32, (rectangle.get_bottom() - 1) as f32);
+            raster.stroke_polyline(&[(left, top), (right, top), (right, bottom), (left, bottom)], true, line_width);
+        }
+        Ok(rectangle.clip(&surface_rectangle))
+    }
+
+    fn circle(
+        &self,
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        center: (i32, i32),
+        radius: i32,
+        line_width: i32,
+    ) -> Result<Rect, GameError> {
+        let rectangle = Rect::new(center.0 - radius, center.1 - radius, radius * 2, radius * 2);
+        let surface_rectangle = surface.get_clip();
+        Raster::new(surface, antialias, color)?.circle(center, radius, line_width);
+        Ok(rectangle.clip(&surface_rectangle))
+    }
+
+    fn ellipse(
+        &self,
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        center: (i32, i32),
+        size: (i32, i32),
+        angle: f32,
+        line_width: i32,
+    ) -> Result<Rect, GameError> {
+        let radius = size.0.max(size.1);
+        let rectangle = Rect::new(center.0 - radius, center.1 - radius, radius * 2, radius * 2);
+        SoftwareDraw::draw_elliptical_arc(surface, antialias, color, center, size, angle, 0.0..360.0, line_width)?;
+        Ok(rectangle.clip(&surface.get_clip()))
+    }
+
+    fn arc(
+        &self,
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        center: (i32, i32),
+        radius: i32,
+        arc_angle: Range<f32>,
+        line_width: i32,
+    ) -> Result<Rect, GameError> {
+        let rectangle = Rect::new(center.0 - radius, center.1 - radius, radius * 2, radius * 2);
+        SoftwareDraw::draw_elliptical_arc(surface, antialias, color, center, (radius, radius), 0.0, arc_angle, line_width)?;
+        Ok(rectangle.clip(&surface.get_clip()))
+    }
+
+    fn elliptical_arc(
+        &self,
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        center: (i32, i32),
+        size: (i32, i32),
+        angle: f32,
+        arc_angle: Range<f32>,
+        line_width: i32,
+    ) -> Result<Rect, GameError> {
+        let radius = size.0.max(size.1);
+        let rectangle = Rect::new(center.0 - radius, center.1 - radius, radius * 2, radius * 2);
+        SoftwareDraw::draw_elliptical_arc(surface, antialias, color, center, size, angle, arc_angle, line_width)?;
+        Ok(rectangle.clip(&surface.get_clip()))
+    }
+
+    fn polygon(
+        &self,
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        points: &Vec<(i32, i32)>,
+        line_width: i32,
+    ) -> Result<Rect, GameError> {
+        let surface_rectangle = surface.get_clip();
+        let mut raster = Raster::new(surface, antialias, color)?;
+        if line_width > 0 {
+            raster.stroke_polyline(&tuple_vec_to_points(points), true, line_width);
+        } else {
+            raster.fill_polygon(&tuple_vec_to_points(points));
+        }
+        Ok(tuple_vec_enclosing_rectangle(points).clip(&surface_rectangle))
+    }
+
+    fn line(
+        &self,
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        start: (i32, i32),
+        end: (i32, i32),
+        line_width: i32,
+    ) -> Result<Rect, GameError> {
+        let surface_rectangle = surface.get_clip();
+        Raster::new(surface, antialias, color)?.stroke_polyline(&tuple_vec_to_points(&[start, end]), false, line_width);
+        Ok(Rect::new_from_points(start, end).clip(&surface_rectangle))
+    }
+
+    fn lines(
+        &self,
+        surface: &mut dyn Surface,
+        antialias: bool,
+        color: &dyn Color,
+        close: bool,
+        points: &Vec<(i32, i32)>,
+        line_width: i32,
+    ) -> Result<Rect, GameError> {
+        let surface_rectangle = surface.get_clip();
+        Raster::new(surface, antialias, color)?.stroke_polyline(&tuple_vec_to_points(points), close, line_width);
+        Ok(tuple_vec_enclosing_rectangle(points).clip(&surface_rectangle))
+    }
+}
+
+#[cfg(test)]
+mod software_draw_test {
+    use super::*;
+    use crate::color::ColorU8;
+    use crate::surface::software_surface::SoftwareSurface;
+    use crate::test::pixel;
+
+    fn red() -> ColorU8 {
+        ColorU8::new_rgb(255, 0, 0)
+    }
+
+    fn count(surface: &dyn Surface) -> usize {
+        surface.raw().unwrap().chunks_exact(4).filter(|p| p[3] > 0).count()
+    }
+
+    #[test]
+    fn rectangle_filled_test() {
+        let mut surface = SoftwareSurface::new((10, 10));
+        let rect = SoftwareDraw {}.rectangle(&mut surface, true, &red(), Rect::new(2, 3, 4, 5), -1).unwrap();
+        assert_eq!((2, 3, 4, 5), (rect.x, rect.y, rect.w, rect.h));
+        assert_eq!(20, count(&surface));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 2, 3));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 5, 7));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 6, 7));
+    }
+
+    #[test]
+    fn rectangle_outline_test() {
+        let mut surface = SoftwareSurface::new((10, 10));
+        SoftwareDraw {}.rectangle(&mut surface, false, &red(), Rect::new(2, 2, 5, 5), 1).unwrap();
+        assert_eq!(16, count(&surface));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 6, 6));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 4, 4));
+    }
+
+    #[test]
+    fn rectangle_clipped_test() {
+        let mut surface = SoftwareSurface::new((10, 10));
+        let rect = SoftwareDraw {}.rectangle(&mut surface, false, &red(), Rect::new(-5, 5, 10, 10), -1).unwrap();
+        assert_eq!((0, 5, 5, 5), (rect.x, rect.y, rect.w, rect.h));
+        assert_eq!(25, count(&surface));
+    }
+
+    #[test]
+    fn clip_test() {
+        let mut surface = SoftwareSurface::new((10, 10));
+        surface.set_clip(Some(Rect::new(2, 2, 4, 4))).unwrap();
+        let rect = SoftwareDraw {}.rectangle(&mut surface, false, &red(), Rect::new(0, 0, 10, 10), -1).unwrap();
+        assert_eq!((2, 2, 4, 4), (rect.x, rect.y, rect.w, rect.h));
+        assert_eq!(16, count(&surface));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 1, 2));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 5, 5));
+        let rect = SoftwareDraw {}.circle(&mut surface, false, &red(), (8, 8), 2, -1).unwrap();
+        assert_eq!((0, 0), (rect.w, rect.h));
+        assert_eq!(16, count(&surface));
+    }
+
+    #[test]
+    fn circle_filled_test() {
+        let mut surface = SoftwareSurface::new((21, 21));
+        SoftwareDraw {}.circle(&mut surface, false, &red(), (10, 10), 10, -1).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 10, 10));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 0, 10));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 20, 10));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 1, 1));
+        let area = count(&surface) as f32;
+        assert!((area - std::f32::consts::PI * 10.5 * 10.5).abs() < 15.0);
+    }
+
+    #[test]
+    fn circle_outline_test() {
+        let mut surface = SoftwareSurface::new((21, 21));
+        SoftwareDraw {}.circle(&mut surface, true, &red(), (10, 10), 8, 2).unwrap();
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 10, 10));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 10, 2));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 10, 0));
+    }
+
+    #[test]
+    fn circle_antialias_test() {
+        let mut surface = SoftwareSurface::new((21, 21));
+        SoftwareDraw {}.circle(&mut surface, true, &red(), (10, 10), 7, -1).unwrap();
+        let edge = pixel(&surface, 15, 15);
+        assert!(edge[3] > 0 && edge[3] < 255);
+    }
+
+    #[test]
+    fn ellipse_test() {
+        let mut surface = SoftwareSurface::new((30, 30));
+        SoftwareDraw {}.ellipse(&mut surface, false, &red(), (15, 15), (10, 4), 0.0, -1).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 24, 15));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 15, 21));
+        let mut rotated = SoftwareSurface::new((30, 30));
+        SoftwareDraw {}.ellipse(&mut rotated, false, &red(), (15, 15), (10, 4), 90.0, -1).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&rotated, 15, 24));
+        assert_eq!([0, 0, 0, 0], pixel(&rotated, 21, 15));
+    }
+
+    #[test]
+    fn arc_test() {
+        let mut surface = SoftwareSurface::new((30, 30));
+        SoftwareDraw {}.arc(&mut surface, false, &red(), (15, 15), 10, 0.0..90.0, -1).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 20, 20));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 10, 20));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 10, 10));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 20, 10));
+    }
+
+    #[test]
+    fn arc_empty_test() {
+        let mut surface = SoftwareSurface::new((30, 30));
+        SoftwareDraw {}.arc(&mut surface, true, &red(), (15, 15), 10, 0.0..0.0, -1).unwrap();
+        assert_eq!(0, count(&surface));
+    }
+
+    #[test]
+    fn elliptical_arc_outline_test() {
+        let mut surface = SoftwareSurface::new((30, 30));
+        SoftwareDraw {}.elliptical_arc(&mut surface, false, &red(), (15, 15), (10, 5), 0.0, 0.0..180.0, 1).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 15, 20));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 15, 10));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 15, 15));
+    }
+
+    #[test]
+    fn polygon_test() {
+        let mut surface = SoftwareSurface::new((20, 20));
+        let rect = SoftwareDraw {}.polygon(&mut surface, false, &red(), &vec![(0, 10), (10, 10), (10, 0)], -1).unwrap();
+        assert_eq!((0, 0, 10, 10), (rect.x, rect.y, rect.w, rect.h));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 8, 8));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 2, 2));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 12, 8));
+    }
+
+    #[test]
+    fn polygon_concave_test() {
+        let mut surface = SoftwareSurface::new((20, 20));
+        let points = vec![(2, 2), (6, 2), (6, 12), (12, 12), (12, 2), (16, 2), (16, 16), (2, 16)];
+        SoftwareDraw {}.polygon(&mut surface, true, &red(), &points, -1).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 4, 8));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 14, 8));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 9, 14));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 9, 6));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 9, 18));
+    }
+
+    #[test]
+    fn polygon_outline_test() {
+        let mut surface = SoftwareSurface::new((20, 20));
+        SoftwareDraw {}.polygon(&mut surface, true, &red(), &vec![(2, 2), (17, 2), (17, 17), (2, 17)], 3).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 10, 2));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 10, 3));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 10, 10));
+    }
+
+    #[test]
+    fn line_test() {
+        let mut surface = SoftwareSurface::new((10, 10));
+        let rect = SoftwareDraw {}.line(&mut surface, false, &red(), (0, 0), (9, 9), 1).unwrap();
+        assert_eq!((0, 0, 9, 9), (rect.x, rect.y, rect.w, rect.h));
+        assert_eq!(10, count(&surface));
+        for i in 0..10 {
+            assert_eq!([255, 0, 0, 255], pixel(&surface, i, i));
+        }
+    }
+
+    #[test]
+    fn line_width_test() {
+        let mut surface = SoftwareSurface::new((10, 10));
+        SoftwareDraw {}.line(&mut surface, false, &red(), (1, 5), (8, 5), 3).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 4, 4));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 4, 6));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 4, 7));
+    }
+
+    #[test]
+    fn line_antialias_test() {
+        let mut surface = SoftwareSurface::new((10, 10));
+        surface.fill(&ColorU8::new_rgb(0, 0, 255)).unwrap();
+        SoftwareDraw {}.line(&mut surface, true, &red(), (0, 2), (9, 5), 1).unwrap();
+        let blended = (0..10).map(|y| pixel(&surface, 4, y)).filter(|p| p[0] > 0 && p[0] < 255).count();
+        assert!(blended > 0);
+        assert!((0..10).all(|y| pixel(&surface, 4, y)[3] == 255));
+    }
+
+    #[test]
+    fn lines_test() {
+        let mut surface = SoftwareSurface::new((10, 10));
+        SoftwareDraw {}.lines(&mut surface, false, &red(), false, &vec![(1, 1), (8, 1), (8, 8)], 1).unwrap();
+        assert_eq!(15, count(&surface));
+        let mut closed = SoftwareSurface::new((10, 10));
+        SoftwareDraw {}.lines(&mut closed, false, &red(), true, &vec![(1, 1), (8, 1), (8, 8)], 1).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&closed, 4, 4));
+    }
+}
@@ -0,0 +1,100 @@
+use crate::surface::BlendMode;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum GameError {
+    RawDataUnavailable,
+    ImageDecode(String),
+    ImageEncode(String),
+    UnsupportedBlendMode(BlendMode),
+    InvalidBlendMode(i32),
+    InvalidGeometry(String),
+    InvalidArgument(String),
+    Unsupported(String),
+    Io(std::io::Error),
+    Backend(String),
+}
+
+impl GameError {
+    pub fn backend<E: fmt::Display>(error: E) -> GameError {
+        GameError::Backend(error.to_string())
+    }
+
+    pub fn image_decode<E: fmt::Display>(error: E) -> GameError {
+        GameError::ImageDecode(error.to_string())
+    }
+
+    pub fn image_encode<E: fmt::Display>(error: E) -> GameError {
+        GameError::ImageEncode(error.to_string())
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::RawDataUnavailable => write!(f, "cannot retrieve raw data"),
+            GameError::ImageDecode(message) => write!(f, "cannot decode image: {}", message),
+            GameError::ImageEncode(message) => write!(f, "cannot encode image: {}", message),
+            GameError::UnsupportedBlendMode(blend_mode) => write!(f, "unsupported blend mode {:?}", blend_mode),
+            GameError::InvalidBlendMode(value) => write!(f, "invalid blend mode {}", value),
+            GameError::InvalidGeometry(message) => write!(f, "invalid geometry: {}", message),
+            GameError::InvalidArgument(message) => write!(f, "invalid argument: {}", message),
+            GameError::Unsupported(message) => write!(f, "not supported: {}", message),
+            GameError::Io(error) => write!(f, "{}", error),
+            GameError::Backend(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl Error for GameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GameError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for GameError {
+    fn from(error: std::io::Error) -> GameError {
+        GameError::Io(error)
+    }
+}
+
+impl From<String> for GameError {
+    fn from(message: String) -> GameError {
+        GameError::Backend(message)
+    }
+}
+
+#[cfg(test)]
+mod game_error_test {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        assert_eq!("cannot retrieve raw data", GameError::RawDataUnavailable.to_string());
+        assert_eq!("unsupported blend mode MultiplyRGBA", GameError::UnsupportedBlendMode(BlendMode::MultiplyRGBA).to_string());
+        assert_eq!("invalid blend mode 42", GameError::InvalidBlendMode(42).to_string());
+        assert_eq!("invalid argument: no color stops", GameError::InvalidArgument("no color stops".to_string()).to_string());
+        assert_eq!("cannot decode image: eof", GameError::image_decode("eof").to_string());
+    }
+
+    #[test]
+    fn from_test() {
+        let error: GameError = String::from("sdl2 error").into();
+        assert!(matches!(error, GameError::Backend(message) if message == "sdl2 error"));
+        let error: GameError = std::io::Error::new(std::io::ErrorKind::NotFound, "not found").into();
+        assert!(matches!(error, GameError::Io(_)));
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn question_mark_test() {
+        fn fail() -> Result<(), GameError> {
+            Err(GameError::InvalidGeometry("negative size".to_string()))?
+        }
+        assert!(matches!(fail(), Err(GameError::InvalidGeometry(_))));
+    }
+}
@@ -1,5 +1,5 @@
+use crate::error::GameError;
 use crate::keys::KeyCode;
-use std::error::Error;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Event {
@@ -13,7 +13,7 @@ pub enum Event {
 }
 
 pub trait Events {
-    fn get(&mut self) -> Result<Vec<Event>, Box<dyn Error>>;
+    fn get(&mut self) -> Result<Vec<Event>, GameError>;
 }
 
 #[cfg(test)]
@@ -23,7 +23,7 @@ mod event_test {
     struct EventMock {}
 
     impl Events for EventMock {
-        fn get(&mut self) -> Result<Vec<Event>, Box<dyn Error>> {
+        fn get(&mut self) -> Result<Vec<Event>, GameError> {
             Ok(vec![])
         }
     }
@@ -0,0 +1,3 @@
+pub mod canvas;
+pub mod context;
+pub mod events;
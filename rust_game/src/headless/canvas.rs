@@ -0,0 +1,71 @@
+use crate::canvas::Canvas;
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::surface::software_surface::SoftwareSurface;
+use crate::surface::{BlendMode, Surface};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FrameCapture {
+    Discard,
+    KeepLast,
+    KeepAll,
+}
+
+pub struct FrameStore {
+    pub frame_capture: FrameCapture,
+    pub frame_count: u64,
+    pub frames: Vec<SoftwareSurface>,
+}
+
+/// The frames are copies of the display surface, which `update` and `update_rects` present the canvas surface on.
+pub struct HeadlessCanvas {
+    pub canvas_surface: SoftwareSurface,
+    display_surface: SoftwareSurface,
+    frame_store: Rc<RefCell<FrameStore>>,
+}
+
+impl HeadlessCanvas {
+    pub fn new(size: (u32, u32), frame_store: Rc<RefCell<FrameStore>>) -> HeadlessCanvas {
+        HeadlessCanvas {
+            canvas_surface: SoftwareSurface::new(size),
+            display_surface: SoftwareSurface::new(size),
+            frame_store,
+        }
+    }
+
+    fn present(&self) -> Result<(), GameError> {
+        let mut frame_store = self.frame_store.borrow_mut();
+        frame_store.frame_count += 1;
+        match frame_store.frame_capture {
+            FrameCapture::Discard => {}
+            FrameCapture::KeepLast => {
+                frame_store.frames.clear();
+                frame_store.frames.push(SoftwareSurface::from_surface(&self.display_surface)?);
+            }
+            FrameCapture::KeepAll => frame_store.frames.push(SoftwareSurface::from_surface(&self.display_surface)?),
+        }
+        Ok(())
+    }
+}
+
+impl Canvas for HeadlessCanvas {
+    fn get_surface(&mut self) -> &mut dyn Surface {
+        &mut self.canvas_surface
+    }
+
+    fn update(&mut self) -> Result<(), GameError> {
+        self.display_surface.blit(&self.canvas_surface, (0, 0), BlendMode::None)?;
+        self.present()
+    }
+
+    fn update_rects(&mut self, rects: &[Rect]) -> Result<(), GameError> {
+        for rect in rects {
+            let rect = self.canvas_surface.get_rect().clip(rect);
+            let region = SoftwareSurface::from_surface_rect(&self.canvas_surface, &rect)?;
+            self.display_surface.blit(&region, rect.get_top_left(), BlendMode::None)?;
+        }
+        self.present()
+    }
+}
@@ -0,0 +1,212 @@
+use crate::canvas::Canvas;
+use crate::color::Color;
+use crate::context::{Context, ContextData};
+use crate::draw::software_draw::SoftwareDraw;
+use crate::draw::Draw;
+use crate::error::GameError;
+use crate::events::{Event, Events};
+use crate::headless::canvas::{FrameCapture, FrameStore, HeadlessCanvas};
+use crate::headless::events::HeadlessEvents;
+use crate::image::Image;
+use crate::surface::software_surface::SoftwareSurface;
+use crate::surface::{Surface, SurfaceBuilder};
+use crate::time::{Time, TimeStd};
+use crate::transform::{SoftwareTransform, Transform};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Context without window and display.
+///
+/// The canvas renders into a `SoftwareSurface` and `update()` keeps or discards the frame according to the `FrameCapture` mode.
+/// The events are taken from a script with one batch of events per call of `Events::get`.
+pub struct HeadlessContext {
+    pub context_data: Arc<ContextData>,
+    canvas_size: (u32, u32),
+    frame_store: Rc<RefCell<FrameStore>>,
+    event_script: Rc<RefCell<VecDeque<Vec<Event>>>>,
+}
+
+impl HeadlessContext {
+    pub fn new(canvas_size: (u32, u32), frame_capture: FrameCapture, event_script: Vec<Vec<Event>>) -> HeadlessContext {
+        HeadlessContext {
+            context_data: Arc::new(ContextData::new()),
+            canvas_size,
+            frame_store: Rc::new(RefCell::new(FrameStore {
+                frame_capture,
+                frame_count: 0,
+                frames: Vec::new(),
+            })),
+            event_script: Rc::new(RefCell::new(event_script.into_iter().collect())),
+        }
+    }
+
+    pub fn get_frame_count(&self) -> u64 {
+        self.frame_store.borrow().frame_count
+    }
+
+    pub fn get_frames(&self) -> Result<Vec<Box<dyn Surface>>, GameError> {
+        self.frame_store.borrow().frames.iter().map(|frame| frame.clone()).collect()
+    }
+
+    pub fn push_events(&self, events: Vec<Event>) {
+        self.event_script.borrow_mut().push_back(events);
+    }
+}
+
+impl SurfaceBuilder for HeadlessContext {
+    fn new_surface_alpha(size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+        SoftwareSurface::new_alpha(size)
+    }
+
+    fn new_surface_with_color(size: (u32, u32), color: &dyn Color) -> Result<Box<dyn Surface>, GameError> {
+        let mut surface = SoftwareSurface::new_alpha(size)?;
+        surface.fill(color)?;
+        Ok(surface)
+    }
+}
+
+impl Context for HeadlessContext {
+    fn new_canvas(&self) -> Result<Box<dyn Canvas>, GameError> {
+        Ok(Box::new(HeadlessCanvas::new(self.canvas_size, self.frame_store.clone())))
+    }
+    fn events(&self) -> Result<Box<dyn Events>, GameError> {
+        Ok(Box::new(HeadlessEvents::new(self.event_script.clone())))
+    }
+    fn time(&self) -> Result<Box<dyn Time>, GameError> {
+        Ok(Box::new(TimeStd::from(self.context_data.clone())?))
+    }
+    fn new_surface_alpha_from_size(&self, size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+        SoftwareSurface::new_alpha(size)
+    }
+    fn draw(&self) -> Result<Box<dyn Draw>, GameError> {
+        SoftwareDraw::new()
+    }
+    fn image(&self) -> Result<Box<dyn Image>, GameError> {
+        Err(GameError::Unsupported("image loading in the headless context".to_string()))?
+    }
+    fn transform(&self) -> Result<Box<dyn Transform>, GameError> {
+        Ok(Box::new(SoftwareTransform {}))
+    }
+}
+
+#[cfg(test)]
+mod headless_context_test {
+    use super::*;
+    use crate::color::ColorU8;
+    use crate::keys::KeyCode;
+    use crate::rectangle::Rect;
+    use crate::sprite::dirty_group::DirtyGroup;
+    use crate::sprite::{DefaultSprite, Group, SpriteGroup};
+    use crate::surface::BlendMode;
+    use crate::test::pixel;
+
+    fn run(context: &HeadlessContext) -> u32 {
+        let mut canvas = context.new_canvas().unwrap();
+        let mut events = context.events().unwrap();
+        let sprite_surface = HeadlessContext::new_surface_with_color((2, 2), &ColorU8::new_rgb(255, 0, 0)).unwrap();
+        let mut sprite_group = Group::new(vec![DefaultSprite::new(sprite_surface, (1, 1))]);
+        let mut frames = 0;
+        'running: loop {
+            for event in events.get().unwrap() {
+                match event {
+                    Event::Quit { .. } => break 'running,
+                    Event::KeyDown {
+                        key: Some(KeyCode::ESC), ..
+                    } => break 'running,
+                    _ => {}
+                }
+            }
+            sprite_group.update().unwrap();
+            canvas.get_surface().fill(&ColorU8::new_gray(0)).unwrap();
+            sprite_group.draw(canvas.get_surface()).unwrap();
+            canvas.update().unwrap();
+            frames += 1;
+        }
+        frames
+    }
+
+    #[test]
+    fn run_until_script_exhausted_test() {
+        let context = HeadlessContext::new((4, 4), FrameCapture::KeepAll, vec![vec![], vec![], vec![]]);
+        assert_eq!(3, run(&context));
+        assert_eq!(3, context.get_frame_count());
+        let frames = context.get_frames().unwrap();
+        assert_eq!(3, frames.len());
+        assert_eq!([255, 0, 0, 255], pixel(frames[2].as_ref(), 1, 1));
+        assert_eq!([0, 0, 0, 255], pixel(frames[2].as_ref(), 2, 2));
+    }
+
+    #[test]
+    fn run_until_key_test() {
+        let context = HeadlessContext::new((4, 4), FrameCapture::KeepLast, vec![]);
+        context.push_events(vec![Event::MouseMotion { pos: (1, 1), rel: (1, 1) }]);
+        context.push_events(vec![Event::KeyDown {
+            key_code: KeyCode::ESC as i32,
+            key: Some(KeyCode::ESC),
+        }]);
+        assert_eq!(1, run(&context));
+        assert_eq!(1, context.get_frames().unwrap().len());
+    }
+
+    #[test]
+    fn discard_frames_test() {
+        let context = HeadlessContext::new((4, 4), FrameCapture::Discard, vec![vec![], vec![]]);
+        assert_eq!(2, run(&context));
+        assert_eq!(2, context.get_frame_count());
+        assert_eq!(0, context.get_frames().unwrap().len());
+    }
+
+    #[test]
+    fn update_rects_test() {
+        let context = HeadlessContext::new((4, 4), FrameCapture::KeepAll, vec![]);
+        let mut canvas = context.new_canvas().unwrap();
+        canvas.get_surface().fill(&ColorU8::new_gray(255)).unwrap();
+        canvas.update_rects(&[Rect::new(1, 1, 2, 1), Rect::new(3, 3, 5, 5)]).unwrap();
+        let frames = context.get_frames().unwrap();
+        assert_eq!(1, frames.len());
+        assert_eq!([255, 255, 255, 255], pixel(frames[0].as_ref(), 1, 1));
+        assert_eq!([255, 255, 255, 255], pixel(frames[0].as_ref(), 2, 1));
+        assert_eq!([255, 255, 255, 255], pixel(frames[0].as_ref(), 3, 3));
+        assert_eq!([0, 0, 0, 0], pixel(frames[0].as_ref(), 0, 0));
+        assert_eq!([0, 0, 0, 0], pixel(frames[0].as_ref(), 1, 2));
+    }
+
+    #[test]
+    fn dirty_group_test() {
+        let context = HeadlessContext::new((8, 8), FrameCapture::KeepAll, vec![]);
+        let mut canvas = context.new_canvas().unwrap();
+        let background = HeadlessContext::new_surface_with_color((8, 8), &ColorU8::new_rgb(0, 0, 255)).unwrap();
+        canvas.get_surface().blit(background.as_ref(), (0, 0), BlendMode::None).unwrap();
+        canvas.update().unwrap();
+        let sprite_surface = HeadlessContext::new_surface_with_color((2, 2), &ColorU8::new_rgb(255, 0, 0)).unwrap();
+        let mut sprite_group = DirtyGroup::new(vec![DefaultSprite::new(sprite_surface, (1, 1))], background);
+        let rects = sprite_group.draw(canvas.get_surface()).unwrap();
+        canvas.update_rects(&rects).unwrap();
+        sprite_group.sprites_mut()[0].set_rect(Rect::new(4, 4, 2, 2));
+        let rects = sprite_group.draw(canvas.get_surface()).unwrap();
+        canvas.update_rects(&rects).unwrap();
+        let frames = context.get_frames().unwrap();
+        assert_eq!(3, frames.len());
+        assert_eq!([255, 0, 0, 255], pixel(frames[1].as_ref(), 0, 0));
+        assert_eq!([0, 0, 255, 255], pixel(frames[2].as_ref(), 0, 0));
+        assert_eq!([255, 0, 0, 255], pixel(frames[2].as_ref(), 5, 5));
+    }
+
+    #[test]
+    fn image_test() {
+        let context = HeadlessContext::new((4, 4), FrameCapture::Discard, vec![]);
+        assert!(matches!(context.image(), Err(GameError::Unsupported(_))));
+    }
+
+    #[test]
+    fn surface_test() {
+        let context = HeadlessContext::new((4, 4), FrameCapture::Discard, vec![]);
+        let surface = context.new_surface_alpha_from_size((3, 2)).unwrap();
+        assert_eq!((3, 2), surface.get_size());
+        let mut canvas = context.new_canvas().unwrap();
+        assert_eq!((4, 4), canvas.get_surface().get_size());
+        canvas.get_surface().blit(surface.as_ref(), (0, 0), BlendMode::Blend).unwrap();
+    }
+}
@@ -0,0 +1,25 @@
+use crate::error::GameError;
+use crate::events::{Event, Events};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+pub struct HeadlessEvents {
+    event_script: Rc<RefCell<VecDeque<Vec<Event>>>>,
+}
+
+impl HeadlessEvents {
+    pub fn new(event_script: Rc<RefCell<VecDeque<Vec<Event>>>>) -> HeadlessEvents {
+        HeadlessEvents { event_script }
+    }
+}
+
+impl Events for HeadlessEvents {
+    /// Returns the next batch of scripted events, or a `Quit` event once the script is exhausted.
+    fn get(&mut self) -> Result<Vec<Event>, GameError> {
+        match self.event_script.borrow_mut().pop_front() {
+            Some(events) => Ok(events),
+            None => Ok(vec![Event::Quit {}]),
+        }
+    }
+}
@@ -1,12 +1,12 @@
+use crate::error::GameError;
 use crate::surface::Surface;
-use std::error::Error;
 use std::path::Path;
 
 pub trait Image {
-    fn load(&self, path: &Path) -> Result<Box<dyn Surface>, Box<dyn Error>>;
-    fn load_frames(&self, _: &Path) -> Result<Vec<Box<dyn Surface>>, Box<dyn Error>>;
-    fn save(&self, surface: &dyn Surface, path: &Path) -> Result<(), Box<dyn Error>>;
-    fn save_frames(&self, frames: &Vec<Box<dyn Surface>>, path: &Path) -> Result<(), Box<dyn Error>>;
+    fn load(&self, path: &Path) -> Result<Box<dyn Surface>, GameError>;
+    fn load_frames(&self, _: &Path) -> Result<Vec<Box<dyn Surface>>, GameError>;
+    fn save(&self, surface: &dyn Surface, path: &Path) -> Result<(), GameError>;
+    fn save_frames(&self, frames: &Vec<Box<dyn Surface>>, path: &Path) -> Result<(), GameError>;
 }
 
 #[cfg(test)]
@@ -16,20 +16,20 @@ mod context_test {
     struct ImageMock {}
 
     impl Image for ImageMock {
-        fn load(&self, _: &Path) -> Result<Box<dyn Surface>, Box<dyn Error>> {
-            Err("test")?
+        fn load(&self, _: &Path) -> Result<Box<dyn Surface>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
-        fn load_frames(&self, _: &Path) -> Result<Vec<Box<dyn Surface>>, Box<dyn Error>> {
-            Err("test")?
+        fn load_frames(&self, _: &Path) -> Result<Vec<Box<dyn Surface>>, GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
-        fn save(&self, _: &dyn Surface, _: &Path) -> Result<(), Box<dyn Error>> {
-            Err("test")?
+        fn save(&self, _: &dyn Surface, _: &Path) -> Result<(), GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
 
-        fn save_frames(&self, _: &Vec<Box<dyn Surface>>, _: &Path) -> Result<(), Box<dyn Error>> {
-            Err("test")?
+        fn save_frames(&self, _: &Vec<Box<dyn Surface>>, _: &Path) -> Result<(), GameError> {
+            Err(GameError::Backend("test".to_string()))?
         }
     }
 }
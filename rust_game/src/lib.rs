@@ -1,15 +1,21 @@
 pub mod canvas;
+pub mod circle;
 pub mod color;
 pub mod context;
 pub mod draw;
+pub mod error;
 pub mod events;
+pub mod headless;
 pub mod image;
 pub mod keys;
+pub mod mask;
 pub mod math;
 pub mod rectangle;
 pub mod sprite;
 pub mod surface;
+pub mod texture;
 pub mod time;
+pub mod transform;
 pub mod utility;
 
 mod test;
@@ -0,0 +1,428 @@
+use crate::color::Color;
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::surface::Surface;
+use std::collections::VecDeque;
+
+// 8 neighbors in clockwise order in screen coordinates, starting with east
+const NEIGHBORS: [(i32, i32); 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
+/// Bitmask for pixel-perfect collision detection.
+///
+/// Positions passed to `overlap*` are the offset of the other mask relative to this mask.
+#[derive(Clone)]
+pub struct Mask {
+    size: (u32, u32),
+    bits: Vec<bool>,
+}
+
+impl Mask {
+    pub fn new(size: (u32, u32), fill: bool) -> Mask {
+        Mask {
+            size,
+            bits: vec![fill; size.0 as usize * size.1 as usize],
+        }
+    }
+
+    /// Sets the bits of the pixels whose alpha is greater than `threshold`.
+    pub fn from_surface(surface: &dyn Surface, threshold: u8) -> Result<Mask, GameError> {
+        Mask::from_pixels(surface, |pixel| pixel[3] > threshold)
+    }
+
+    /// Sets the bits of the pixels whose color differs from the color key, the alpha channel is ignored.
+    pub fn from_color_key(surface: &dyn Surface, color_key: &dyn Color) -> Result<Mask, GameError> {
+        let key = [color_key.r(), color_key.g(), color_key.b()];
+        Mask::from_pixels(surface, |pixel| pixel[..3] != key)
+    }
+
+    fn from_pixels<F: Fn(&[u8]) -> bool>(surface: &dyn Surface, is_set: F) -> Result<Mask, GameError> {
+        let size = surface.get_size();
+        let (w, pitch) = (size.0 as usize, surface.get_pitch());
+        let raw = surface.raw()?;
+        if size.1 > 0 && raw.len() < (size.1 as usize - 1) * pitch + w * 4 {
+            Err(GameError::RawDataUnavailable)?
+        }
+        let bits = (0..size.1 as usize).flat_map(|y| raw[y * pitch..y * pitch + w * 4].chunks_exact(4)).map(is_set).collect();
+        Ok(Mask { size, bits })
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.size.0
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.size.1
+    }
+
+    pub fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    pub fn get_rect(&self) -> Rect {
+        Rect::new(0, 0, self.size.0 as i32, self.size.1 as i32)
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x >= 0 && y >= 0 && (x as u32) < self.size.0 && (y as u32) < self.size.1 {
+            Some(y as usize * self.size.0 as usize + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `false` for positions outside of the mask.
+    pub fn get_at(&self, x: i32, y: i32) -> bool {
+        self.index(x, y).is_some_and(|i| self.bits[i])
+    }
+
+    /// Positions outside of the mask are ignored.
+    pub fn set_at(&mut self, x: i32, y: i32, value: bool) {
+        if let Some(i) = self.index(x, y) {
+            self.bits[i] = value;
+        }
+    }
+
+    pub fn fill(&mut self) {
+        self.bits.iter_mut().for_each(|bit| *bit = true);
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.iter_mut().for_each(|bit| *bit = false);
+    }
+
+    pub fn invert(&mut self) {
+        self.bits.iter_mut().for_each(|bit| *bit = !*bit);
+    }
+
+    pub fn count(&self) -> u32 {
+        self.bits.iter().filter(|&&bit| bit).count() as u32
+    }
+
+    fn set_positions(&self) -> impl Iterator<Item = (i32, i32)> + '_ {
+        let width = self.size.0 as usize;
+        self.bits.iter().enumerate().filter(|(_, &bit)| bit).map(move |(i, _)| ((i % width) as i32, (i / width) as i32))
+    }
+
+    // positions of this mask which overlap the other mask
+    fn overlap_positions<'a>(&'a self, other: &'a Mask, offset: (i32, i32)) -> impl Iterator<Item = (i32, i32)> + 'a {
+        let area = self.get_rect().clip(&other.get_rect().move_(offset.0, offset.1));
+        (area.get_top()..area.get_top() + area.get_height())
+            .flat_map(move |y| (area.get_left()..area.get_left() + area.get_width()).map(move |x| (x, y)))
+            .filter(move |&(x, y)| self.get_at(x, y) && other.get_at(x - offset.0, y - offset.1))
+    }
+
+    /// First overlapping position in row order, in the coordinates of this mask.
+    pub fn overlap(&self, other: &Mask, offset: (i32, i32)) -> Option<(i32, i32)> {
+        self.overlap_positions(other, offset).next()
+    }
+
+    pub fn overlap_area(&self, other: &Mask, offset: (i32, i32)) -> u32 {
+        self.overlap_positions(other, offset).count() as u32
+    }
+
+    /// Mask with the size of this mask, where the bits of both masks are set.
+    pub fn overlap_mask(&self, other: &Mask, offset: (i32, i32)) -> Mask {
+        let mut mask = Mask::new(self.size, false);
+        for (x, y) in self.overlap_positions(other, offset) {
+            mask.set_at(x, y, true);
+        }
+        mask
+    }
+
+    /// Clockwise outline of the first connected component in row order, usable as `Draw::polygon` points.
+    pub fn outline(&self) -> Vec<(i32, i32)> {
+        let start = match self.set_positions().next() {
+            Some(start) => start,
+            None => return Vec::new(),
+        };
+        let mut points = vec![start];
+        let mut current = start;
+        // the pixel left of the first pixel in row order is never set
+        let mut backtrack_direction = 4;
+        let mut first_next = None;
+        loop {
+            let next = (1..=8).map(|k| (backtrack_direction + k) % 8).find(|&d| {
+                let (dx, dy) = NEIGHBORS[d];
+                self.get_at(current.0 + dx, current.1 + dy)
+            });
+            let direction = match next {
+                Some(direction) => direction,
+                None => break,
+            };
+            let next = (current.0 + NEIGHBORS[direction].0, current.1 + NEIGHBORS[direction].1);
+            if current == start && first_next == Some(next) {
+                break;
+            }
+            first_next.get_or_insert(next);
+            let previous = (direction + 7) % 8;
+            let backtrack = (current.0 + NEIGHBORS[previous].0, current.1 + NEIGHBORS[previous].1);
+            let relative = (backtrack.0 - next.0, backtrack.1 - next.1);
+            backtrack_direction = NEIGHBORS.iter().position(|&n| n == relative).unwrap_or(4);
+            current = next;
+            points.push(current);
+        }
+        if points.len() > 1 && points.last() == Some(&start) {
+            points.pop();
+        }
+        points
+    }
+
+    /// Mean position of the set bits, `None` if no bit is set.
+    pub fn centroid(&self) -> Option<(i32, i32)> {
+        let (count, sum_x, sum_y) =
+            self.set_positions().fold((0i64, 0i64, 0i64), |(n, sx, sy), (x, y)| (n + 1, sx + x as i64, sy + y as i64));
+        if count == 0 {
+            None
+        } else {
+            Some(((sum_x / count) as i32, (sum_y / count) as i32))
+        }
+    }
+
+    /// Bounding rectangle of all set bits, empty if no bit is set.
+    pub fn get_bounding_rect(&self) -> Rect {
+        let mut positions = self.set_positions();
+        match positions.next() {
+            Some((x, y)) => {
+                let rect = Rect::new(x, y, 1, 1);
+                positions.fold(rect, |rect, (x, y)| rect.union(&Rect::new(x, y, 1, 1)))
+            }
+            None => Rect::new(0, 0, 0, 0),
+        }
+    }
+
+    // flood fill of the 8-connected component which contains `start`
+    fn flood_fill(&self, start: (i32, i32), visited: &mut [bool]) -> Vec<(i32, i32)> {
+        let mut component = Vec::new();
+        let mut queue = VecDeque::from([start]);
+        if let Some(i) = self.index(start.0, start.1) {
+            visited[i] = true;
+        }
+        while let Some((x, y)) = queue.pop_front() {
+            component.push((x, y));
+            for (dx, dy) in NEIGHBORS {
+                if let Some(i) = self.index(x + dx, y + dy) {
+                    if self.bits[i] && !visited[i] {
+                        visited[i] = true;
+                        queue.push_back((x + dx, y + dy));
+                    }
+                }
+            }
+        }
+        component
+    }
+
+    fn components(&self) -> Vec<Vec<(i32, i32)>> {
+        let mut visited = vec![false; self.bits.len()];
+        let starts: Vec<(i32, i32)> = self.set_positions().collect();
+        let mut components = Vec::new();
+        for start in starts {
+            if !visited[self.index(start.0, start.1).unwrap()] {
+                components.push(self.flood_fill(start, &mut visited));
+            }
+        }
+        components
+    }
+
+    fn mask_from_positions(&self, positions: &[(i32, i32)]) -> Mask {
+        let mut mask = Mask::new(self.size, false);
+        for &(x, y) in positions {
+            mask.set_at(x, y, true);
+        }
+        mask
+    }
+
+    /// The 8-connected component which contains the position, empty if the bit at the position is not set.
+    pub fn connected_component(&self, x: i32, y: i32) -> Mask {
+        if !self.get_at(x, y) {
+            return Mask::new(self.size, false);
+        }
+        let mut visited = vec![false; self.bits.len()];
+        self.mask_from_positions(&self.flood_fill((x, y), &mut visited))
+    }
+
+    /// All 8-connected components with at least `min_size` bits, in row order of their first bit.
+    pub fn connected_components(&self, min_size: u32) -> Vec<Mask> {
+        self.components()
+            .iter()
+            .filter(|component| component.len() as u32 >= min_size)
+            .map(|component| self.mask_from_positions(component))
+            .collect()
+    }
+
+    /// Bounding rectangles of the 8-connected components, in row order of their first bit.
+    pub fn bounding_rects(&self) -> Vec<Rect> {
+        self.components()
+            .iter()
+            .map(|component| {
+                let (x, y) = component[0];
+                component.iter().fold(Rect::new(x, y, 1, 1), |rect, &(x, y)| rect.union(&Rect::new(x, y, 1, 1)))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod mask_test {
+    use super::*;
+    use crate::color::ColorU8;
+    use crate::surface::software_surface::SoftwareSurface;
+
+    fn mask_from_rows(rows: &[&str]) -> Mask {
+        let mut mask = Mask::new((rows[0].len() as u32, rows.len() as u32), false);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                mask.set_at(x as i32, y as i32, c == '#');
+            }
+        }
+        mask
+    }
+
+    fn assert_equal_rect(rect: &Rect, x: i32, y: i32, w: i32, h: i32) {
+        assert_eq!((x, y, w, h), (rect.x, rect.y, rect.w, rect.h));
+    }
+
+    #[test]
+    fn new_test() {
+        let mask = Mask::new((3, 2), true);
+        assert_eq!((3, 2), mask.get_size());
+        assert_eq!(6, mask.count());
+        assert_eq!(0, Mask::new((3, 2), false).count());
+    }
+
+    #[test]
+    fn from_surface_test() {
+        let mut surface = SoftwareSurface::new((2, 2));
+        surface.raw_mut().unwrap()[3] = 255;
+        surface.raw_mut().unwrap()[7] = 127;
+        surface.raw_mut().unwrap()[11] = 128;
+        let mask = Mask::from_surface(&surface, 127).unwrap();
+        assert!(mask.get_at(0, 0));
+        assert!(!mask.get_at(1, 0));
+        assert!(mask.get_at(0, 1));
+        assert!(!mask.get_at(1, 1));
+    }
+
+    #[test]
+    fn from_color_key_test() {
+        let mut surface = SoftwareSurface::new((2, 1));
+        surface.fill(&ColorU8::new_rgb(255, 0, 255)).unwrap();
+        surface.raw_mut().unwrap()[4..8].copy_from_slice(&[10, 20, 30, 0]);
+        let mask = Mask::from_color_key(&surface, &ColorU8::new_rgba(255, 0, 255, 0)).unwrap();
+        assert!(!mask.get_at(0, 0));
+        assert!(mask.get_at(1, 0));
+    }
+
+    #[test]
+    fn get_set_at_test() {
+        let mut mask = Mask::new((2, 2), false);
+        mask.set_at(1, 0, true);
+        mask.set_at(5, 5, true);
+        assert!(mask.get_at(1, 0));
+        assert!(!mask.get_at(0, 0));
+        assert!(!mask.get_at(-1, 0));
+        assert!(!mask.get_at(2, 0));
+        assert_eq!(1, mask.count());
+    }
+
+    #[test]
+    fn fill_clear_invert_test() {
+        let mut mask = Mask::new((2, 2), false);
+        mask.fill();
+        assert_eq!(4, mask.count());
+        mask.clear();
+        assert_eq!(0, mask.count());
+        mask.set_at(0, 0, true);
+        mask.invert();
+        assert_eq!(3, mask.count());
+        assert!(!mask.get_at(0, 0));
+    }
+
+    #[test]
+    fn overlap_test() {
+        let ring = mask_from_rows(&["###", "#.#", "###"]);
+        let dot = mask_from_rows(&["#"]);
+        assert_eq!(None, ring.overlap(&dot, (1, 1)));
+        assert_eq!(Some((2, 1)), ring.overlap(&dot, (2, 1)));
+        assert_eq!(None, ring.overlap(&dot, (3, 1)));
+        assert_eq!(Some((0, 0)), ring.overlap(&ring, (0, 0)));
+        assert_eq!(Some((2, 0)), ring.overlap(&ring, (2, -2)));
+        assert_eq!(Some((0, 0)), ring.overlap(&ring, (-1, 0)));
+        assert_eq!(Some((0, 1)), ring.overlap(&ring, (-1, 1)));
+    }
+
+    #[test]
+    fn overlap_area_test() {
+        let ring = mask_from_rows(&["###", "#.#", "###"]);
+        assert_eq!(8, ring.overlap_area(&ring, (0, 0)));
+        assert_eq!(4, ring.overlap_area(&ring, (1, 0)));
+        assert_eq!(1, ring.overlap_area(&ring, (2, 2)));
+        assert_eq!(0, ring.overlap_area(&ring, (3, 0)));
+    }
+
+    #[test]
+    fn overlap_mask_test() {
+        let ring = mask_from_rows(&["###", "#.#", "###"]);
+        let overlap = ring.overlap_mask(&Mask::new((2, 2), true), (1, 1));
+        assert_eq!((3, 3), overlap.get_size());
+        assert_eq!(3, overlap.count());
+        assert!(overlap.get_at(2, 1));
+        assert!(overlap.get_at(1, 2));
+        assert!(overlap.get_at(2, 2));
+    }
+
+    #[test]
+    fn outline_test() {
+        let square = mask_from_rows(&["....", ".##.", ".##.", "...."]);
+        assert_eq!(vec![(1, 1), (2, 1), (2, 2), (1, 2)], square.outline());
+        let ring = mask_from_rows(&["###", "#.#", "###"]);
+        assert_eq!(vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2), (1, 2), (0, 2), (0, 1)], ring.outline());
+        let diagonal = mask_from_rows(&["#..", ".#.", "..#"]);
+        assert_eq!(vec![(0, 0), (1, 1), (2, 2), (1, 1)], diagonal.outline());
+        assert_eq!(vec![(1, 0)], mask_from_rows(&[".#."]).outline());
+        assert!(Mask::new((2, 2), false).outline().is_empty());
+    }
+
+    #[test]
+    fn centroid_test() {
+        assert_eq!(Some((1, 1)), mask_from_rows(&["###", "#.#", "###"]).centroid());
+        assert_eq!(Some((3, 0)), mask_from_rows(&["..###"]).centroid());
+        assert_eq!(None, Mask::new((2, 2), false).centroid());
+    }
+
+    #[test]
+    fn get_bounding_rect_test() {
+        let mask = mask_from_rows(&[".....", ".#...", "...#.", "....."]);
+        assert_equal_rect(&mask.get_bounding_rect(), 1, 1, 3, 2);
+        assert_equal_rect(&Mask::new((2, 2), false).get_bounding_rect(), 0, 0, 0, 0);
+    }
+
+    #[test]
+    fn connected_component_test() {
+        let mask = mask_from_rows(&["##...", ".#..#", "....#"]);
+        let component = mask.connected_component(1, 1);
+        assert_eq!(3, component.count());
+        assert!(component.get_at(0, 0));
+        assert!(!component.get_at(4, 1));
+        assert_eq!(0, mask.connected_component(2, 0).count());
+    }
+
+    #[test]
+    fn connected_components_test() {
+        let mask = mask_from_rows(&["##...", ".#..#", "#...#", "..#.."]);
+        let components = mask.connected_components(0);
+        assert_eq!(3, components.len());
+        assert_eq!(vec![4, 2, 1], components.iter().map(|c| c.count()).collect::<Vec<u32>>());
+        assert_eq!(2, mask.connected_components(2).len());
+        let diagonal = mask_from_rows(&["#..", ".#.", "..#"]);
+        assert_eq!(1, diagonal.connected_components(0).len());
+    }
+
+    #[test]
+    fn bounding_rects_test() {
+        let mask = mask_from_rows(&["##...", ".#..#", "....#"]);
+        let rects = mask.bounding_rects();
+        assert_eq!(2, rects.len());
+        assert_equal_rect(&rects[0], 0, 0, 2, 2);
+        assert_equal_rect(&rects[1], 4, 1, 1, 2);
+    }
+}
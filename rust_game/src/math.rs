@@ -2,3 +2,303 @@ use glm;
 
 pub type Vec2 = glm::Vector2<f32>;
 pub type Vec3 = glm::Vector3<f32>;
+
+/// Points which can be used with the geometry functions, e.g. `Vec2` or the `(i32, i32)` points of `Draw::polygon`.
+pub trait ToVec2 {
+    fn to_vec2(&self) -> Vec2;
+}
+
+impl ToVec2 for Vec2 {
+    fn to_vec2(&self) -> Vec2 {
+        *self
+    }
+}
+
+impl ToVec2 for (i32, i32) {
+    fn to_vec2(&self) -> Vec2 {
+        Vec2::new(self.0 as f32, self.1 as f32)
+    }
+}
+
+impl ToVec2 for (f32, f32) {
+    fn to_vec2(&self) -> Vec2 {
+        Vec2::new(self.0, self.1)
+    }
+}
+
+/// Orientation of a polygon in screen coordinates, where the y axis points down.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+    Degenerate,
+}
+
+const EPSILON: f32 = 1e-6;
+
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+// parameters `(t, u)` of the intersection `p + t * r = q + u * s`, `None` if the lines are parallel
+fn intersection_parameters(p: Vec2, r: Vec2, q: Vec2, s: Vec2) -> Option<(f32, f32)> {
+    let denominator = cross(r, s);
+    if denominator.abs() < EPSILON {
+        return None;
+    }
+    let qp = q - p;
+    Some((cross(qp, s) / denominator, cross(qp, r) / denominator))
+}
+
+fn in_unit_range(t: f32) -> bool {
+    (-EPSILON..=1.0 + EPSILON).contains(&t)
+}
+
+/// Intersection of the infinite lines through `a1`, `a2` and `b1`, `b2`.
+pub fn line_intersection<P: ToVec2>(a1: P, a2: P, b1: P, b2: P) -> Option<Vec2> {
+    let (p, q) = (a1.to_vec2(), b1.to_vec2());
+    let r = a2.to_vec2() - p;
+    let (t, _) = intersection_parameters(p, r, q, b2.to_vec2() - q)?;
+    Some(p + r * t)
+}
+
+/// Intersection of the line segments `a1`-`a2` and `b1`-`b2`. Collinear segments do not intersect.
+pub fn segment_intersection<P: ToVec2>(a1: P, a2: P, b1: P, b2: P) -> Option<Vec2> {
+    let (p, q) = (a1.to_vec2(), b1.to_vec2());
+    let r = a2.to_vec2() - p;
+    let (t, u) = intersection_parameters(p, r, q, b2.to_vec2() - q)?;
+    if in_unit_range(t) && in_unit_range(u) {
+        Some(p + r * t)
+    } else {
+        None
+    }
+}
+
+/// Intersection of the ray starting at `origin` in `direction` with the line segment `b1`-`b2`.
+pub fn ray_segment_intersection<P: ToVec2>(origin: P, direction: P, b1: P, b2: P) -> Option<Vec2> {
+    let (p, q) = (origin.to_vec2(), b1.to_vec2());
+    let r = direction.to_vec2();
+    let (t, u) = intersection_parameters(p, r, q, b2.to_vec2() - q)?;
+    if t >= -EPSILON && in_unit_range(u) {
+        Some(p + r * t)
+    } else {
+        None
+    }
+}
+
+/// Intersection points of the line segment `a1`-`a2` with the outline of a circle, ordered from `a1` to `a2`.
+pub fn segment_circle_intersection<P: ToVec2>(a1: P, a2: P, center: P, radius: f32) -> Vec<Vec2> {
+    let p = a1.to_vec2();
+    let d = a2.to_vec2() - p;
+    let f = p - center.to_vec2();
+    let a = glm::dot(d, d);
+    if a < EPSILON {
+        return Vec::new();
+    }
+    let b = 2.0 * glm::dot(f, d);
+    let c = glm::dot(f, f) - radius * radius;
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return Vec::new();
+    }
+    let root = discriminant.sqrt();
+    let mut parameters = vec![(-b - root) / (2.0 * a)];
+    if root > EPSILON {
+        parameters.push((-b + root) / (2.0 * a));
+    }
+    parameters.into_iter().filter(|&t| in_unit_range(t)).map(|t| p + d * t).collect()
+}
+
+/// Point on the line segment `a1`-`a2` with the shortest distance to `point`.
+pub fn closest_point_on_segment<P: ToVec2>(point: P, a1: P, a2: P) -> Vec2 {
+    let p = a1.to_vec2();
+    let d = a2.to_vec2() - p;
+    let length_squared = glm::dot(d, d);
+    if length_squared < EPSILON {
+        return p;
+    }
+    let t = (glm::dot(point.to_vec2() - p, d) / length_squared).clamp(0.0, 1.0);
+    p + d * t
+}
+
+/// Tests if `point` is inside the triangle or on its border. The triangle can have either winding.
+pub fn point_in_triangle<P: ToVec2>(point: P, a: P, b: P, c: P) -> bool {
+    let (p, a, b, c) = (point.to_vec2(), a.to_vec2(), b.to_vec2(), c.to_vec2());
+    let d1 = cross(b - a, p - a);
+    let d2 = cross(c - b, p - b);
+    let d3 = cross(a - c, p - c);
+    let has_negative = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+    let has_positive = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+    !(has_negative && has_positive)
+}
+
+/// Tests if `point` is inside the convex polygon or on its border. The polygon can have either winding.
+pub fn point_in_convex_polygon<P: ToVec2>(point: P, polygon: &[P]) -> bool {
+    if polygon.len() < 3 {
+        return false;
+    }
+    let p = point.to_vec2();
+    let (mut has_negative, mut has_positive) = (false, false);
+    for i in 0..polygon.len() {
+        let a = polygon[i].to_vec2();
+        let b = polygon[(i + 1) % polygon.len()].to_vec2();
+        let d = cross(b - a, p - a);
+        has_negative |= d < 0.0;
+        has_positive |= d > 0.0;
+    }
+    !(has_negative && has_positive)
+}
+
+/// Signed area of the polygon (shoelace formula), positive for a clockwise polygon in screen coordinates.
+pub fn polygon_signed_area<P: ToVec2>(polygon: &[P]) -> f32 {
+    let mut area = 0.0;
+    for i in 0..polygon.len() {
+        let a = polygon[i].to_vec2();
+        let b = polygon[(i + 1) % polygon.len()].to_vec2();
+        area += cross(a, b);
+    }
+    area / 2.0
+}
+
+pub fn polygon_area<P: ToVec2>(polygon: &[P]) -> f32 {
+    polygon_signed_area(polygon).abs()
+}
+
+/// Center of mass of the polygon area, `None` if the polygon has no area.
+pub fn polygon_centroid<P: ToVec2>(polygon: &[P]) -> Option<Vec2> {
+    let area = polygon_signed_area(polygon);
+    if area.abs() < EPSILON {
+        return None;
+    }
+    let mut centroid = Vec2::new(0.0, 0.0);
+    for i in 0..polygon.len() {
+        let a = polygon[i].to_vec2();
+        let b = polygon[(i + 1) % polygon.len()].to_vec2();
+        centroid = centroid + (a + b) * cross(a, b);
+    }
+    Some(centroid * (1.0 / (6.0 * area)))
+}
+
+pub fn polygon_winding<P: ToVec2>(polygon: &[P]) -> Winding {
+    let area = polygon_signed_area(polygon);
+    if area > EPSILON {
+        Winding::Clockwise
+    } else if area < -EPSILON {
+        Winding::CounterClockwise
+    } else {
+        Winding::Degenerate
+    }
+}
+
+#[cfg(test)]
+mod math_test {
+    use super::*;
+
+    fn assert_near(v: Vec2, x: f32, y: f32) {
+        assert!((v.x - x).abs() < 1e-4 && (v.y - y).abs() < 1e-4, "({}, {}) != ({}, {})", v.x, v.y, x, y);
+    }
+
+    #[test]
+    fn to_vec2_test() {
+        assert!((3, -4).to_vec2() == Vec2::new(3.0, -4.0));
+        assert!((0.5f32, 1.5f32).to_vec2() == Vec2::new(0.5, 1.5));
+        assert!(Vec2::new(1.0, 2.0).to_vec2() == Vec2::new(1.0, 2.0));
+    }
+
+    #[test]
+    fn line_intersection_test() {
+        assert_near(line_intersection((0, 0), (10, 10), (0, 10), (10, 0)).unwrap(), 5.0, 5.0);
+        assert_near(line_intersection((0, 0), (1, 1), (0, 10), (1, 9)).unwrap(), 5.0, 5.0);
+        assert!(line_intersection((0, 0), (10, 0), (0, 5), (10, 5)).is_none());
+    }
+
+    #[test]
+    fn segment_intersection_test() {
+        assert_near(segment_intersection((0, 0), (10, 10), (0, 10), (10, 0)).unwrap(), 5.0, 5.0);
+        assert_near(segment_intersection((0, 0), (10, 0), (10, 0), (10, 10)).unwrap(), 10.0, 0.0);
+        assert!(segment_intersection((0, 0), (1, 1), (0, 10), (1, 9)).is_none());
+        assert!(segment_intersection((0, 0), (10, 0), (0, 5), (10, 5)).is_none());
+    }
+
+    #[test]
+    fn ray_segment_intersection_test() {
+        let origin = Vec2::new(0.0, 5.0);
+        let hit = ray_segment_intersection(origin, Vec2::new(1.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0));
+        assert_near(hit.unwrap(), 10.0, 5.0);
+        assert!(ray_segment_intersection(origin, Vec2::new(-1.0, 0.0), Vec2::new(10.0, 0.0), Vec2::new(10.0, 10.0)).is_none());
+        assert!(ray_segment_intersection(origin, Vec2::new(1.0, 0.0), Vec2::new(10.0, 6.0), Vec2::new(10.0, 10.0)).is_none());
+    }
+
+    #[test]
+    fn segment_circle_intersection_test() {
+        let points = segment_circle_intersection((-10, 0), (10, 0), (0, 0), 5.0);
+        assert_eq!(2, points.len());
+        assert_near(points[0], -5.0, 0.0);
+        assert_near(points[1], 5.0, 0.0);
+        let points = segment_circle_intersection((0, 0), (10, 0), (0, 0), 5.0);
+        assert_eq!(1, points.len());
+        assert_near(points[0], 5.0, 0.0);
+        let points = segment_circle_intersection((-10, 5), (10, 5), (0, 0), 5.0);
+        assert_eq!(1, points.len());
+        assert_near(points[0], 0.0, 5.0);
+        assert!(segment_circle_intersection((-10, 6), (10, 6), (0, 0), 5.0).is_empty());
+        assert!(segment_circle_intersection((-1, 0), (1, 0), (0, 0), 5.0).is_empty());
+    }
+
+    #[test]
+    fn closest_point_on_segment_test() {
+        assert_near(closest_point_on_segment((5, 5), (0, 0), (10, 0)), 5.0, 0.0);
+        assert_near(closest_point_on_segment((-5, 5), (0, 0), (10, 0)), 0.0, 0.0);
+        assert_near(closest_point_on_segment((15, -5), (0, 0), (10, 0)), 10.0, 0.0);
+        assert_near(closest_point_on_segment((15, -5), (3, 3), (3, 3)), 3.0, 3.0);
+    }
+
+    #[test]
+    fn point_in_triangle_test() {
+        assert!(point_in_triangle((2, 2), (0, 0), (10, 0), (0, 10)));
+        assert!(point_in_triangle((2, 2), (0, 0), (0, 10), (10, 0)));
+        assert!(point_in_triangle((5, 5), (0, 0), (10, 0), (0, 10)));
+        assert!(point_in_triangle((0, 0), (0, 0), (10, 0), (0, 10)));
+        assert!(!point_in_triangle((6, 6), (0, 0), (10, 0), (0, 10)));
+        assert!(!point_in_triangle((-1, 2), (0, 0), (10, 0), (0, 10)));
+    }
+
+    #[test]
+    fn point_in_convex_polygon_test() {
+        let hexagon = [(10, 0), (20, 5), (20, 15), (10, 20), (0, 15), (0, 5)];
+        assert!(point_in_convex_polygon((10, 10), &hexagon));
+        assert!(point_in_convex_polygon((20, 10), &hexagon));
+        assert!(!point_in_convex_polygon((1, 1), &hexagon));
+        assert!(!point_in_convex_polygon((21, 10), &hexagon));
+        let reversed: Vec<(i32, i32)> = hexagon.iter().rev().cloned().collect();
+        assert!(point_in_convex_polygon((10, 10), &reversed));
+        assert!(!point_in_convex_polygon((1, 1), &[(0, 0), (1, 1)]));
+    }
+
+    #[test]
+    fn polygon_area_test() {
+        assert_eq!(100.0, polygon_area(&[(0, 0), (10, 0), (10, 10), (0, 10)]));
+        assert_eq!(100.0, polygon_area(&[(0, 10), (10, 10), (10, 0), (0, 0)]));
+        assert_eq!(50.0, polygon_area(&[(0, 0), (10, 0), (0, 10)]));
+        assert_eq!(0.0, polygon_area(&[(0, 0), (10, 0)]));
+        assert_eq!(100.0, polygon_signed_area(&[(0, 0), (10, 0), (10, 10), (0, 10)]));
+        assert_eq!(-100.0, polygon_signed_area(&[(0, 10), (10, 10), (10, 0), (0, 0)]));
+    }
+
+    #[test]
+    fn polygon_centroid_test() {
+        assert_near(polygon_centroid(&[(0, 0), (10, 0), (10, 10), (0, 10)]).unwrap(), 5.0, 5.0);
+        assert_near(polygon_centroid(&[(0, 0), (0, 9), (9, 0)]).unwrap(), 3.0, 3.0);
+        let l_shape = [(0, 0), (20, 0), (20, 10), (10, 10), (10, 20), (0, 20)];
+        assert_near(polygon_centroid(&l_shape).unwrap(), 25.0 / 3.0, 25.0 / 3.0);
+        assert!(polygon_centroid(&[(0, 0), (10, 10), (20, 20)]).is_none());
+    }
+
+    #[test]
+    fn polygon_winding_test() {
+        assert_eq!(Winding::Clockwise, polygon_winding(&[(0, 0), (10, 0), (10, 10)]));
+        assert_eq!(Winding::CounterClockwise, polygon_winding(&[(0, 0), (10, 10), (10, 0)]));
+        assert_eq!(Winding::Degenerate, polygon_winding(&[(0, 0), (10, 10), (20, 20)]));
+        assert_eq!(Winding::Clockwise, polygon_winding(&[Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0)]));
+    }
+}
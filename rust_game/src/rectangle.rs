@@ -1,3 +1,6 @@
+pub mod frect;
+
+use crate::rectangle::frect::FRect;
 use std::cmp;
 
 // TODO trait Rect
@@ -264,11 +267,54 @@ impl Rect {
             None
         }
     }
-    // union
-    // union_ip
-    // unionall
-    // unionall_ip
-    // fit
+
+    pub fn clip_line_f32(&self, start: (f32, f32), end: (f32, f32)) -> Option<((f32, f32), (f32, f32))> {
+        FRect::from(self).clip_line(start, end)
+    }
+
+    pub fn union(&self, rect: &Rect) -> Rect {
+        let mut union_rect = self.clone();
+        union_rect.union_ip(rect);
+        union_rect
+    }
+
+    pub fn union_ip(&mut self, rect: &Rect) {
+        let x = cmp::min(self.get_left(), rect.get_left());
+        let y = cmp::min(self.get_top(), rect.get_top());
+        let right = cmp::max(self.get_right(), rect.get_right());
+        let bottom = cmp::max(self.get_bottom(), rect.get_bottom());
+        self.update(x, y, right - x, bottom - y);
+    }
+
+    pub fn union_all(&self, rects: &[Rect]) -> Rect {
+        let mut union_rect = self.clone();
+        union_rect.union_all_ip(rects);
+        union_rect
+    }
+
+    pub fn union_all_ip(&mut self, rects: &[Rect]) {
+        for rect in rects {
+            self.union_ip(rect);
+        }
+    }
+
+    // scales the rectangle to fit into `rect`, keeping the aspect ratio, and centers it in `rect`
+    pub fn fit(&self, rect: &Rect) -> Rect {
+        let ratio = (self.w as f32 / rect.w as f32).max(self.h as f32 / rect.h as f32);
+        let w = (self.w as f32 / ratio).round() as i32;
+        let h = (self.h as f32 / ratio).round() as i32;
+        Rect::new(rect.x + (rect.w - w) / 2, rect.y + (rect.h - h) / 2, w, h)
+    }
+
+    // scales the size of the rectangle, the center is kept
+    pub fn scale_by(&self, x: f32, y: f32) -> Rect {
+        let size = ((self.w as f32 * x).round() as i32, (self.h as f32 * y).round() as i32);
+        Rect::new_center(self.get_center(), size)
+    }
+
+    pub fn scale_by_ip(&mut self, x: f32, y: f32) {
+        *self = self.scale_by(x, y);
+    }
 
     pub fn normalize(&mut self) {
         if self.w < 0 {
@@ -299,8 +345,21 @@ impl Rect {
             && rect.get_top() < self.get_bottom()
     }
 
-    // collidelist
-    // collidelistall
+    pub fn collide_list(&self, rects: &[Rect]) -> Option<usize> {
+        rects.iter().position(|rect| self.collide_rect(rect))
+    }
+
+    pub fn collide_list_all(&self, rects: &[Rect]) -> Vec<usize> {
+        rects.iter().enumerate().filter(|(_, rect)| self.collide_rect(rect)).map(|(i, _)| i).collect()
+    }
+
+    // collects the keys of all colliding rectangles, e.g. from `&HashMap<K, Rect>` or `&BTreeMap<K, Rect>`
+    pub fn collide_map<'a, K, I>(&self, rects: I) -> Vec<K>
+    where
+        I: IntoIterator<Item = (K, &'a Rect)>,
+    {
+        rects.into_iter().filter(|(_, rect)| self.collide_rect(rect)).map(|(key, _)| key).collect()
+    }
 }
 
 #[cfg(test)]
@@ -668,6 +727,72 @@ mod rect_test {
         assert!(Rect::new(10, 10, 10, 10).clip_line((0, 30), (30, 0)) == Some(((10, 20), (20, 10))));
     }
 
+    #[test]
+    fn clip_line_f32_test() {
+        let rect = Rect::new(10, 10, 10, 10);
+        assert!(rect.clip_line_f32((20.5, 20.5), (30.0, 30.0)).is_none());
+        assert!(rect.clip_line_f32((0.0, 0.0), (30.0, 30.0)) == Some(((10.0, 10.0), (20.0, 20.0))));
+        assert!(rect.clip_line_f32((0.0, 15.5), (15.5, 15.5)) == Some(((10.0, 15.5), (15.5, 15.5))));
+    }
+
+    #[test]
+    fn union_test() {
+        let rect1 = Rect::new(10, 10, 10, 10).union(&Rect::new(15, 5, 10, 10));
+        assert_equal_rect(&rect1, 10, 5, 15, 15);
+        let rect2 = Rect::new(10, 10, 10, 10).union(&Rect::new(12, 12, 2, 2));
+        assert_equal_rect(&rect2, 10, 10, 10, 10);
+        let rect3 = Rect::new(10, 10, 10, 10).union(&Rect::new(-10, 30, 5, 5));
+        assert_equal_rect(&rect3, -10, 10, 30, 25);
+    }
+
+    #[test]
+    fn union_ip_test() {
+        let mut rect = Rect::new(10, 10, 10, 10);
+        rect.union_ip(&Rect::new(15, 5, 10, 10));
+        assert_equal_rect(&rect, 10, 5, 15, 15);
+    }
+
+    #[test]
+    fn union_all_test() {
+        let rects = vec![Rect::new(0, 20, 5, 5), Rect::new(30, 0, 5, 5), Rect::new(12, 12, 2, 2)];
+        let rect1 = Rect::new(10, 10, 10, 10).union_all(&rects);
+        assert_equal_rect(&rect1, 0, 0, 35, 25);
+        let rect2 = Rect::new(10, 10, 10, 10).union_all(&[]);
+        assert_equal_rect(&rect2, 10, 10, 10, 10);
+    }
+
+    #[test]
+    fn union_all_ip_test() {
+        let mut rect = Rect::new(10, 10, 10, 10);
+        rect.union_all_ip(&[Rect::new(0, 20, 5, 5), Rect::new(30, 0, 5, 5)]);
+        assert_equal_rect(&rect, 0, 0, 35, 25);
+    }
+
+    #[test]
+    fn fit_test() {
+        let rect1 = Rect::new(0, 0, 20, 10).fit(&Rect::new(10, 10, 100, 100));
+        assert_equal_rect(&rect1, 10, 35, 100, 50);
+        let rect2 = Rect::new(0, 0, 10, 20).fit(&Rect::new(10, 10, 100, 100));
+        assert_equal_rect(&rect2, 35, 10, 50, 100);
+        let rect3 = Rect::new(0, 0, 400, 300).fit(&Rect::new(0, 0, 40, 40));
+        assert_equal_rect(&rect3, 0, 5, 40, 30);
+    }
+
+    #[test]
+    fn scale_by_test() {
+        let rect1 = Rect::new(10, 10, 10, 20).scale_by(2.0, 0.5);
+        assert_equal_rect(&rect1, 5, 15, 20, 10);
+        let rect2 = Rect::new(10, 10, 10, 10).scale_by(1.0, 1.0);
+        assert_equal_rect(&rect2, 10, 10, 10, 10);
+    }
+
+    #[test]
+    fn scale_by_ip_test() {
+        let mut rect = Rect::new(10, 10, 10, 20);
+        rect.scale_by_ip(2.0, 0.5);
+        assert_equal_rect(&rect, 5, 15, 20, 10);
+    }
+
     #[test]
     fn normalize_test() {
         let mut rect1 = Rect::new(10, 10, 10, 10);
@@ -713,4 +838,34 @@ mod rect_test {
         assert!(Rect::new(10, 10, 10, 10).collide_rect(&Rect::new(20, 10, 10, 10)) == false);
         assert!(Rect::new(10, 10, 10, 10).collide_rect(&Rect::new(15, 15, 10, 10)) == true);
     }
+
+    #[test]
+    fn collide_list_test() {
+        let rects = vec![Rect::new(0, 0, 5, 5), Rect::new(15, 15, 10, 10), Rect::new(12, 12, 2, 2)];
+        assert!(Rect::new(10, 10, 10, 10).collide_list(&rects) == Some(1));
+        assert!(Rect::new(30, 30, 10, 10).collide_list(&rects).is_none());
+        assert!(Rect::new(10, 10, 10, 10).collide_list(&[]).is_none());
+    }
+
+    #[test]
+    fn collide_list_all_test() {
+        let rects = vec![Rect::new(0, 0, 5, 5), Rect::new(15, 15, 10, 10), Rect::new(12, 12, 2, 2)];
+        assert!(Rect::new(10, 10, 10, 10).collide_list_all(&rects) == vec![1, 2]);
+        assert!(Rect::new(0, 0, 30, 30).collide_list_all(&rects) == vec![0, 1, 2]);
+        assert!(Rect::new(30, 30, 10, 10).collide_list_all(&rects).is_empty());
+    }
+
+    #[test]
+    fn collide_map_test() {
+        let mut rects = std::collections::BTreeMap::new();
+        rects.insert("a", Rect::new(0, 0, 5, 5));
+        rects.insert("b", Rect::new(15, 15, 10, 10));
+        rects.insert("c", Rect::new(12, 12, 2, 2));
+        assert!(Rect::new(10, 10, 10, 10).collide_map(&rects) == vec![&"b", &"c"]);
+        assert!(Rect::new(30, 30, 10, 10).collide_map(&rects).is_empty());
+        let mut ids = std::collections::HashMap::new();
+        ids.insert(7u32, Rect::new(0, 0, 5, 5));
+        let keys: Vec<u32> = Rect::new(0, 0, 1, 1).collide_map(ids.iter().map(|(&id, rect)| (id, rect)));
+        assert!(keys == vec![7]);
+    }
 }
@@ -0,0 +1,598 @@
+use crate::math::Vec2;
+use crate::rectangle::Rect;
+
+#[derive(Clone)]
+pub struct FRect {
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+impl FRect {
+    pub fn new(x: f32, y: f32, w: f32, h: f32) -> FRect {
+        FRect { x, y, w, h }
+    }
+
+    pub fn new_from_points(p1: (f32, f32), p2: (f32, f32)) -> FRect {
+        let x1 = p1.0.min(p2.0);
+        let y1 = p1.1.min(p2.1);
+        let x2 = p1.0.max(p2.0);
+        let y2 = p1.1.max(p2.1);
+        FRect {
+            x: x1,
+            y: y1,
+            w: x2 - x1,
+            h: y2 - y1,
+        }
+    }
+
+    pub fn new_center(center: (f32, f32), size: (f32, f32)) -> FRect {
+        FRect {
+            x: center.0 - size.0 / 2.0,
+            y: center.1 - size.1 / 2.0,
+            w: size.0,
+            h: size.1,
+        }
+    }
+
+    pub fn new_from_vec2(top_left: Vec2, size: Vec2) -> FRect {
+        FRect::new(top_left.x, top_left.y, size.x, size.y)
+    }
+
+    // rounds the edges of the rectangle to the nearest pixel, integral rectangles are converted without loss
+    pub fn to_rect(&self) -> Rect {
+        let left = self.get_left().round() as i32;
+        let top = self.get_top().round() as i32;
+        let right = self.get_right().round() as i32;
+        let bottom = self.get_bottom().round() as i32;
+        Rect::new(left, top, right - left, bottom - top)
+    }
+
+    pub fn get_width(&self) -> f32 {
+        self.w
+    }
+
+    pub fn set_width(&mut self, width: f32) {
+        self.w = width;
+    }
+
+    pub fn get_height(&self) -> f32 {
+        self.h
+    }
+
+    pub fn set_height(&mut self, height: f32) {
+        self.h = height;
+    }
+
+    pub fn get_left(&self) -> f32 {
+        self.x
+    }
+
+    pub fn set_left(&mut self, left: f32) {
+        self.x = left;
+    }
+
+    pub fn get_right(&self) -> f32 {
+        self.x + self.w
+    }
+
+    pub fn set_right(&mut self, right: f32) {
+        self.x = right - self.w;
+    }
+
+    pub fn get_top(&self) -> f32 {
+        self.y
+    }
+
+    pub fn set_top(&mut self, top: f32) {
+        self.y = top;
+    }
+
+    pub fn get_bottom(&self) -> f32 {
+        self.y + self.h
+    }
+
+    pub fn set_bottom(&mut self, bottom: f32) {
+        self.y = bottom - self.h
+    }
+
+    pub fn get_center_x(&self) -> f32 {
+        self.x + self.w / 2.0
+    }
+
+    pub fn set_center_x(&mut self, center_x: f32) {
+        self.x = center_x - self.w / 2.0;
+    }
+
+    pub fn get_center_y(&self) -> f32 {
+        self.y + self.h / 2.0
+    }
+
+    pub fn set_center_y(&mut self, center_y: f32) {
+        self.y = center_y - self.h / 2.0
+    }
+
+    pub fn get_size(&self) -> (f32, f32) {
+        (self.w, self.h)
+    }
+
+    pub fn set_size(&mut self, size: (f32, f32)) {
+        self.w = size.0;
+        self.h = size.1;
+    }
+
+    pub fn get_size_vec2(&self) -> Vec2 {
+        Vec2::new(self.w, self.h)
+    }
+
+    pub fn set_size_vec2(&mut self, size: Vec2) {
+        self.w = size.x;
+        self.h = size.y;
+    }
+
+    pub fn get_top_left(&self) -> (f32, f32) {
+        (self.x, self.y)
+    }
+
+    pub fn set_top_left(&mut self, top_left: (f32, f32)) {
+        self.x = top_left.0;
+        self.y = top_left.1;
+    }
+
+    pub fn get_top_left_vec2(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    pub fn set_top_left_vec2(&mut self, top_left: Vec2) {
+        self.x = top_left.x;
+        self.y = top_left.y;
+    }
+
+    pub fn get_top_right(&self) -> (f32, f32) {
+        (self.x + self.w, self.y)
+    }
+
+    pub fn set_top_right(&mut self, top_right: (f32, f32)) {
+        self.x = top_right.0 - self.w;
+        self.y = top_right.1;
+    }
+
+    pub fn get_bottom_left(&self) -> (f32, f32) {
+        (self.x, self.y + self.h)
+    }
+
+    pub fn set_bottom_left(&mut self, bottom_left: (f32, f32)) {
+        self.x = bottom_left.0;
+        self.y = bottom_left.1 - self.h;
+    }
+
+    pub fn get_bottom_right(&self) -> (f32, f32) {
+        (self.x + self.w, self.y + self.h)
+    }
+
+    pub fn set_bottom_right(&mut self, bottom_right: (f32, f32)) {
+        self.x = bottom_right.0 - self.w;
+        self.y = bottom_right.1 - self.h;
+    }
+
+    pub fn get_center(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    pub fn set_center(&mut self, center: (f32, f32)) {
+        self.x = center.0 - self.w / 2.0;
+        self.y = center.1 - self.h / 2.0;
+    }
+
+    pub fn get_center_vec2(&self) -> Vec2 {
+        Vec2::new(self.x + self.w / 2.0, self.y + self.h / 2.0)
+    }
+
+    pub fn set_center_vec2(&mut self, center: Vec2) {
+        self.x = center.x - self.w / 2.0;
+        self.y = center.y - self.h / 2.0;
+    }
+
+    pub fn get_mid_top(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y)
+    }
+
+    pub fn set_mid_top(&mut self, mid_top: (f32, f32)) {
+        self.x = mid_top.0 - self.w / 2.0;
+        self.y = mid_top.1;
+    }
+
+    pub fn get_mid_left(&self) -> (f32, f32) {
+        (self.x, self.y + self.h / 2.0)
+    }
+
+    pub fn set_mid_left(&mut self, mid_left: (f32, f32)) {
+        self.x = mid_left.0;
+        self.y = mid_left.1 - self.h / 2.0;
+    }
+
+    pub fn get_mid_bottom(&self) -> (f32, f32) {
+        (self.x + self.w / 2.0, self.y + self.h)
+    }
+
+    pub fn set_mid_bottom(&mut self, mid_bottom: (f32, f32)) {
+        self.x = mid_bottom.0 - self.w / 2.0;
+        self.y = mid_bottom.1 - self.h;
+    }
+
+    pub fn get_mid_right(&self) -> (f32, f32) {
+        (self.x + self.w, self.y + self.h / 2.0)
+    }
+
+    pub fn set_mid_right(&mut self, mid_right: (f32, f32)) {
+        self.x = mid_right.0 - self.w;
+        self.y = mid_right.1 - self.h / 2.0;
+    }
+
+    pub fn move_(&self, x: f32, y: f32) -> FRect {
+        FRect::new(self.x + x, self.y + y, self.w, self.h)
+    }
+
+    pub fn move_ip(&mut self, x: f32, y: f32) {
+        self.x += x;
+        self.y += y;
+    }
+
+    pub fn inflate(&self, x: f32, y: f32) -> FRect {
+        FRect::new(self.x - x / 2.0, self.y - y / 2.0, self.w + x, self.h + y)
+    }
+
+    pub fn inflate_ip(&mut self, x: f32, y: f32) {
+        self.x -= x / 2.0;
+        self.y -= y / 2.0;
+        self.w += x;
+        self.h += y;
+    }
+
+    pub fn update(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.x = x;
+        self.y = y;
+        self.w = width;
+        self.h = height;
+    }
+
+    pub fn clamp(&self, rect: &FRect) -> FRect {
+        let mut clamped = self.clone();
+        clamped.clamp_ip(rect);
+        clamped
+    }
+
+    pub fn clamp_ip(&mut self, rect: &FRect) {
+        self.x = rect.x.max((self.x + self.w).min(rect.x + rect.w) - self.w);
+        self.y = rect.y.max((self.y + self.h).min(rect.y + rect.h) - self.h);
+        if self.w > rect.w {
+            self.x -= (self.w - rect.w) / 2.0;
+        }
+        if self.h > rect.h {
+            self.y -= (self.h - rect.h) / 2.0;
+        }
+    }
+
+    pub fn clip(&self, rect: &FRect) -> FRect {
+        if self.collide_rect(rect) {
+            let x = self.x.max(rect.x);
+            let y = self.y.max(rect.y);
+            let w = self.get_right().min(rect.get_right()) - x;
+            let h = self.get_bottom().min(rect.get_bottom()) - y;
+            FRect::new(x, y, w, h)
+        } else {
+            FRect::new(self.x, self.y, 0.0, 0.0)
+        }
+    }
+
+    // Liang-Barsky clipping of the line segment against the rectangle
+    pub fn clip_line(&self, start: (f32, f32), end: (f32, f32)) -> Option<((f32, f32), (f32, f32))> {
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let (mut t0, mut t1) = (0.0f32, 1.0f32);
+        for (p, q) in [
+            (-dx, start.0 - self.get_left()),
+            (dx, self.get_right() - start.0),
+            (-dy, start.1 - self.get_top()),
+            (dy, self.get_bottom() - start.1),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return None;
+        }
+        Some(((start.0 + t0 * dx, start.1 + t0 * dy), (start.0 + t1 * dx, start.1 + t1 * dy)))
+    }
+
+    pub fn normalize(&mut self) {
+        if self.w < 0.0 {
+            self.x += self.w;
+            self.w *= -1.0;
+        }
+        if self.h < 0.0 {
+            self.y += self.h;
+            self.h *= -1.0;
+        }
+    }
+
+    pub fn contains(&self, rect: &FRect) -> bool {
+        self.get_left() <= rect.get_left()
+            && self.get_right() >= rect.get_right()
+            && self.get_top() <= rect.get_top()
+            && self.get_bottom() >= rect.get_bottom()
+    }
+
+    pub fn collide_point(&self, x: f32, y: f32) -> bool {
+        self.get_left() <= x && x <= self.get_right() && self.get_top() <= y && y <= self.get_bottom()
+    }
+
+    pub fn collide_rect(&self, rect: &FRect) -> bool {
+        self.get_left() < rect.get_right()
+            && rect.get_left() < self.get_right()
+            && self.get_top() < rect.get_bottom()
+            && rect.get_top() < self.get_bottom()
+    }
+}
+
+impl From<&Rect> for FRect {
+    fn from(rect: &Rect) -> FRect {
+        FRect::new(rect.x as f32, rect.y as f32, rect.w as f32, rect.h as f32)
+    }
+}
+
+impl From<&FRect> for Rect {
+    fn from(rect: &FRect) -> Rect {
+        rect.to_rect()
+    }
+}
+
+#[cfg(test)]
+mod frect_test {
+    use super::*;
+
+    fn assert_equal_frect(rect: &FRect, x: f32, y: f32, w: f32, h: f32) {
+        assert!(rect.x == x);
+        assert!(rect.y == y);
+        assert!(rect.w == w);
+        assert!(rect.h == h);
+    }
+
+    #[test]
+    fn new_test() {
+        let rect = FRect::new(1.5, 2.5, 3.5, 4.5);
+        assert_equal_frect(&rect, 1.5, 2.5, 3.5, 4.5);
+    }
+
+    #[test]
+    fn new_form_points_test() {
+        assert_equal_frect(&FRect::new_from_points((10.0, 10.0), (20.0, 25.0)), 10.0, 10.0, 10.0, 15.0);
+        assert_equal_frect(&FRect::new_from_points((20.0, 10.0), (10.0, 25.0)), 10.0, 10.0, 10.0, 15.0);
+        assert_equal_frect(&FRect::new_from_points((10.0, 25.0), (20.0, 10.0)), 10.0, 10.0, 10.0, 15.0);
+        assert_equal_frect(&FRect::new_from_points((20.0, 25.0), (10.0, 10.0)), 10.0, 10.0, 10.0, 15.0);
+    }
+
+    #[test]
+    fn new_center_test() {
+        let rect = FRect::new_center((10.0, 10.0), (5.0, 3.0));
+        assert_equal_frect(&rect, 7.5, 8.5, 5.0, 3.0);
+    }
+
+    #[test]
+    fn new_from_vec2_test() {
+        let rect = FRect::new_from_vec2(Vec2::new(1.5, 2.5), Vec2::new(3.0, 4.0));
+        assert_equal_frect(&rect, 1.5, 2.5, 3.0, 4.0);
+    }
+
+    #[test]
+    fn from_rect_test() {
+        let rect = FRect::from(&Rect::new(-4, 5, 6, 7));
+        assert_equal_frect(&rect, -4.0, 5.0, 6.0, 7.0);
+        let rect = Rect::from(&rect);
+        assert!((rect.x, rect.y, rect.w, rect.h) == (-4, 5, 6, 7));
+    }
+
+    #[test]
+    fn to_rect_test() {
+        let rect = FRect::new(0.4, 0.6, 2.2, 2.0).to_rect();
+        assert!((rect.x, rect.y, rect.w, rect.h) == (0, 1, 3, 2));
+    }
+
+    #[test]
+    fn get_size_test() {
+        let rect = FRect::new(0.0, 0.0, 10.5, 20.5);
+        assert!(rect.get_size() == (10.5, 20.5));
+        assert!(rect.get_size_vec2() == Vec2::new(10.5, 20.5));
+    }
+
+    #[test]
+    fn set_size_test() {
+        let mut rect = FRect::new(1.0, 2.0, 0.0, 0.0);
+        rect.set_size((30.0, 40.0));
+        assert_equal_frect(&rect, 1.0, 2.0, 30.0, 40.0);
+        rect.set_size_vec2(Vec2::new(3.0, 4.0));
+        assert_equal_frect(&rect, 1.0, 2.0, 3.0, 4.0);
+    }
+
+    #[test]
+    fn get_edges_test() {
+        let rect = FRect::new(4.0, 5.0, 6.0, 7.0);
+        assert!(rect.get_left() == 4.0);
+        assert!(rect.get_right() == 10.0);
+        assert!(rect.get_top() == 5.0);
+        assert!(rect.get_bottom() == 12.0);
+        assert!(rect.get_center_x() == 7.0);
+        assert!(rect.get_center_y() == 8.5);
+    }
+
+    #[test]
+    fn set_edges_test() {
+        let mut rect = FRect::new(4.0, 5.0, 6.0, 7.0);
+        rect.set_right(1.0);
+        rect.set_bottom(1.0);
+        assert_equal_frect(&rect, -5.0, -6.0, 6.0, 7.0);
+        rect.set_left(1.0);
+        rect.set_top(2.0);
+        assert_equal_frect(&rect, 1.0, 2.0, 6.0, 7.0);
+        rect.set_center_x(1.0);
+        rect.set_center_y(1.0);
+        assert_equal_frect(&rect, -2.0, -2.5, 6.0, 7.0);
+    }
+
+    #[test]
+    fn get_anchors_test() {
+        let rect = FRect::new(5.0, 6.0, 7.0, 8.0);
+        assert!(rect.get_top_left() == (5.0, 6.0));
+        assert!(rect.get_top_right() == (12.0, 6.0));
+        assert!(rect.get_bottom_left() == (5.0, 14.0));
+        assert!(rect.get_bottom_right() == (12.0, 14.0));
+        assert!(rect.get_center() == (8.5, 10.0));
+        assert!(rect.get_mid_top() == (8.5, 6.0));
+        assert!(rect.get_mid_left() == (5.0, 10.0));
+        assert!(rect.get_mid_bottom() == (8.5, 14.0));
+        assert!(rect.get_mid_right() == (12.0, 10.0));
+        assert!(rect.get_top_left_vec2() == Vec2::new(5.0, 6.0));
+        assert!(rect.get_center_vec2() == Vec2::new(8.5, 10.0));
+    }
+
+    #[test]
+    fn set_anchors_test() {
+        let mut rect = FRect::new(5.0, 6.0, 7.0, 8.0);
+        rect.set_top_left((11.0, 12.0));
+        assert_equal_frect(&rect, 11.0, 12.0, 7.0, 8.0);
+        rect.set_top_right((11.0, 12.0));
+        assert_equal_frect(&rect, 4.0, 12.0, 7.0, 8.0);
+        rect.set_bottom_left((11.0, 12.0));
+        assert_equal_frect(&rect, 11.0, 4.0, 7.0, 8.0);
+        rect.set_bottom_right((11.0, 12.0));
+        assert_equal_frect(&rect, 4.0, 4.0, 7.0, 8.0);
+        rect.set_center((11.0, 12.0));
+        assert_equal_frect(&rect, 7.5, 8.0, 7.0, 8.0);
+        rect.set_mid_top((11.0, 12.0));
+        assert_equal_frect(&rect, 7.5, 12.0, 7.0, 8.0);
+        rect.set_mid_left((11.0, 12.0));
+        assert_equal_frect(&rect, 11.0, 8.0, 7.0, 8.0);
+        rect.set_mid_bottom((11.0, 12.0));
+        assert_equal_frect(&rect, 7.5, 4.0, 7.0, 8.0);
+        rect.set_mid_right((11.0, 12.0));
+        assert_equal_frect(&rect, 4.0, 8.0, 7.0, 8.0);
+        rect.set_top_left_vec2(Vec2::new(1.0, 2.0));
+        assert_equal_frect(&rect, 1.0, 2.0, 7.0, 8.0);
+        rect.set_center_vec2(Vec2::new(1.0, 2.0));
+        assert_equal_frect(&rect, -2.5, -2.0, 7.0, 8.0);
+    }
+
+    #[test]
+    fn move_test() {
+        let rect = FRect::new(0.0, 0.0, 1.0, 2.0).move_(0.25, 0.5);
+        assert_equal_frect(&rect, 0.25, 0.5, 1.0, 2.0);
+    }
+
+    #[test]
+    fn move_ip_test() {
+        let mut rect = FRect::new(0.0, 0.0, 1.0, 2.0);
+        rect.move_ip(0.25, 0.5);
+        rect.move_ip(0.25, 0.5);
+        assert_equal_frect(&rect, 0.5, 1.0, 1.0, 2.0);
+    }
+
+    #[test]
+    fn inflate_test() {
+        let rect = FRect::new(0.0, 0.0, 1.0, 2.0).inflate(3.0, 5.0);
+        assert_equal_frect(&rect, -1.5, -2.5, 4.0, 7.0);
+    }
+
+    #[test]
+    fn inflate_ip_test() {
+        let mut rect = FRect::new(0.0, 0.0, 1.0, 2.0);
+        rect.inflate_ip(3.0, 5.0);
+        assert_equal_frect(&rect, -1.5, -2.5, 4.0, 7.0);
+    }
+
+    #[test]
+    fn update_test() {
+        let mut rect = FRect::new(0.0, 0.0, 0.0, 0.0);
+        rect.update(1.0, 2.0, 3.0, 4.0);
+        assert_equal_frect(&rect, 1.0, 2.0, 3.0, 4.0);
+    }
+
+    #[test]
+    fn clamp_test() {
+        let bounds = FRect::new(20.0, 20.0, 100.0, 100.0);
+        assert_equal_frect(&FRect::new(10.0, 20.0, 10.0, 10.0).clamp(&bounds), 20.0, 20.0, 10.0, 10.0);
+        assert_equal_frect(&FRect::new(200.0, 20.0, 10.0, 10.0).clamp(&bounds), 110.0, 20.0, 10.0, 10.0);
+        assert_equal_frect(&FRect::new(20.0, 200.0, 10.0, 10.0).clamp(&bounds), 20.0, 110.0, 10.0, 10.0);
+        assert_equal_frect(&FRect::new(50.5, 50.5, 10.0, 10.0).clamp(&bounds), 50.5, 50.5, 10.0, 10.0);
+        let small = FRect::new(100.0, 100.0, 10.0, 10.0);
+        assert_equal_frect(&FRect::new(20.0, 20.0, 21.0, 21.0).clamp(&small), 94.5, 94.5, 21.0, 21.0);
+    }
+
+    #[test]
+    fn clamp_ip_test() {
+        let mut rect = FRect::new(200.0, 200.0, 20.0, 20.0);
+        rect.clamp_ip(&FRect::new(100.0, 100.0, 10.0, 10.0));
+        assert_equal_frect(&rect, 95.0, 95.0, 20.0, 20.0);
+    }
+
+    #[test]
+    fn clip_test() {
+        let rect1 = FRect::new(15.0, 15.0, 5.0, 5.0).clip(&FRect::new(10.0, 10.0, 10.0, 10.0));
+        assert_equal_frect(&rect1, 15.0, 15.0, 5.0, 5.0);
+        let rect2 = FRect::new(8.5, 15.0, 5.0, 5.0).clip(&FRect::new(10.0, 10.0, 10.0, 10.0));
+        assert_equal_frect(&rect2, 10.0, 15.0, 3.5, 5.0);
+        let rect3 = FRect::new(15.0, 18.5, 5.0, 5.0).clip(&FRect::new(10.0, 10.0, 10.0, 10.0));
+        assert_equal_frect(&rect3, 15.0, 18.5, 5.0, 1.5);
+        let rect4 = FRect::new(25.0, 25.0, 5.0, 5.0).clip(&FRect::new(10.0, 10.0, 10.0, 10.0));
+        assert_equal_frect(&rect4, 25.0, 25.0, 0.0, 0.0);
+    }
+
+    #[test]
+    fn clip_line_test() {
+        let rect = FRect::new(10.0, 10.0, 10.0, 10.0);
+        assert!(rect.clip_line((25.0, 0.0), (25.0, 30.0)).is_none());
+        assert!(rect.clip_line((0.0, 30.0), (30.0, 29.0)).is_none());
+        assert!(rect.clip_line((0.0, 0.0), (30.0, 30.0)) == Some(((10.0, 10.0), (20.0, 20.0))));
+        assert!(rect.clip_line((30.0, 0.0), (0.0, 30.0)) == Some(((20.0, 10.0), (10.0, 20.0))));
+        assert!(rect.clip_line((0.0, 15.0), (15.0, 15.0)) == Some(((10.0, 15.0), (15.0, 15.0))));
+        assert!(rect.clip_line((12.0, 12.0), (14.0, 18.0)) == Some(((12.0, 12.0), (14.0, 18.0))));
+        assert!(rect.clip_line((0.0, 10.0), (20.0, 30.0)) == Some(((10.0, 20.0), (10.0, 20.0))));
+    }
+
+    #[test]
+    fn normalize_test() {
+        let mut rect = FRect::new(10.0, 10.0, -10.0, -5.5);
+        rect.normalize();
+        assert_equal_frect(&rect, 0.0, 4.5, 10.0, 5.5);
+    }
+
+    #[test]
+    fn contains_test() {
+        assert!(FRect::new(10.0, 10.0, 10.0, 10.0).contains(&FRect::new(10.0, 10.0, 10.0, 10.0)));
+        assert!(FRect::new(10.0, 10.0, 10.0, 10.0).contains(&FRect::new(10.5, 10.5, 9.5, 9.5)));
+        assert!(!FRect::new(10.0, 10.0, 10.0, 10.0).contains(&FRect::new(9.5, 10.0, 10.0, 10.0)));
+        assert!(!FRect::new(10.0, 10.0, 10.0, 10.0).contains(&FRect::new(10.0, 10.5, 10.0, 10.0)));
+    }
+
+    #[test]
+    fn collide_point_test() {
+        assert!(FRect::new(10.0, 10.0, 10.0, 10.0).collide_point(15.0, 15.0));
+        assert!(FRect::new(10.0, 10.0, 10.0, 10.0).collide_point(10.0, 20.0));
+        assert!(!FRect::new(10.0, 10.0, 10.0, 10.0).collide_point(9.9, 15.0));
+        assert!(!FRect::new(10.0, 10.0, 10.0, 10.0).collide_point(15.0, 20.1));
+    }
+
+    #[test]
+    fn collide_rect_test() {
+        assert!(!FRect::new(10.0, 10.0, 10.0, 10.0).collide_rect(&FRect::new(20.0, 20.0, 10.0, 10.0)));
+        assert!(!FRect::new(10.0, 10.0, 10.0, 10.0).collide_rect(&FRect::new(20.0, 10.0, 10.0, 10.0)));
+        assert!(FRect::new(10.0, 10.0, 10.0, 10.0).collide_rect(&FRect::new(19.5, 19.5, 10.0, 10.0)));
+        assert!(FRect::new(10.0, 10.0, 10.0, 10.0).collide_rect(&FRect::new(15.0, 15.0, 10.0, 10.0)));
+    }
+}
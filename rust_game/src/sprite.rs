@@ -1,13 +1,45 @@
 pub mod animation;
+pub mod dirty_group;
+pub mod layered_group;
 
+use crate::circle::Circle;
+use crate::error::GameError;
+use crate::mask::Mask;
 use crate::rectangle::Rect;
+use crate::sprite::layered_group::DEFAULT_LAYER;
 use crate::surface::{BlendMode, Surface};
-use std::error::Error;
+use crate::transform;
+use std::borrow::Cow;
+use std::cell::OnceCell;
 
 pub trait Sprite {
     fn image<'a>(&'a self) -> &'a dyn Surface;
     fn rect<'a>(&'a self) -> &'a Rect;
     fn set_rect(&mut self, rect: Rect);
+    /// Mask for `collide_mask`, without a mask it is created from the image on every collision test.
+    fn mask(&self) -> Option<&Mask> {
+        Option::None
+    }
+    /// Counterclockwise rotation of the image around the center of `rect()` in degrees.
+    fn angle(&self) -> f32 {
+        0.0
+    }
+    fn set_angle(&mut self, _angle: f32) {}
+    fn scale(&self) -> f32 {
+        1.0
+    }
+    fn set_scale(&mut self, _scale: f32) {}
+    /// Layer in a `LayeredGroup`, sprites in higher layers are drawn on top.
+    fn layer(&self) -> i32;
+    fn set_layer(&mut self, layer: i32);
+    /// Radius for circle collisions, by default the radius of the circle which encloses `rect()`.
+    fn radius(&self) -> f32 {
+        let (w, h) = self.rect().get_size();
+        ((w * w + h * h) as f32).sqrt() / 2.0
+    }
+    fn circle(&self) -> Circle {
+        Circle::new_center(self.rect().get_center(), self.radius().round() as i32)
+    }
     fn image_animation<'a>(&'a mut self) -> Option<(&'a mut dyn ImageAnimation, &'a dyn Surface)> {
         Option::None
     }
@@ -18,7 +50,7 @@ pub trait Sprite {
     fn is_killed(&self) -> bool {
         false
     }
-    fn update(&mut self) -> Result<(), Box<dyn Error>> {
+    fn update(&mut self) -> Result<(), GameError> {
         Ok(())
     }
 }
@@ -29,6 +61,10 @@ pub struct DefaultSprite {
     image_animation: Option<Box<dyn ImageAnimation>>,
     rectangle_animation: Option<Box<dyn RectAnimation>>,
     is_killed: bool,
+    angle: f32,
+    scale: f32,
+    layer: i32,
+    mask: OnceCell<Option<Mask>>,
 }
 
 impl DefaultSprite {
@@ -40,6 +76,10 @@ impl DefaultSprite {
             image_animation: Option::None,
             rectangle_animation: Option::None,
             is_killed: false,
+            angle: 0.0,
+            scale: 1.0,
+            layer: DEFAULT_LAYER,
+            mask: OnceCell::new(),
         })
     }
 
@@ -55,8 +95,19 @@ impl DefaultSprite {
             image_animation,
             rectangle_animation,
             is_killed: false,
+            angle: 0.0,
+            scale: 1.0,
+            layer: DEFAULT_LAYER,
+            mask: OnceCell::new(),
         })
     }
+
+    // the rectangle is the bounding box of the rotated and scaled image, the cached mask is created again when needed
+    fn update_rectangle_size(&mut self) {
+        let size = transform::rotozoom_size(self.image.get_size(), self.angle, self.scale);
+        self.rectangle = Rect::new_center(self.rectangle.get_center(), (size.0 as i32, size.1 as i32));
+        self.mask = OnceCell::new();
+    }
 }
 
 impl Sprite for DefaultSprite {
@@ -72,6 +123,10 @@ impl Sprite for DefaultSprite {
         self.rectangle = rect;
     }
 
+    fn mask(&self) -> Option<&Mask> {
+        self.mask.get_or_init(|| image_mask(self)).as_ref()
+    }
+
     fn image_animation<'a>(&'a mut self) -> Option<(&'a mut dyn ImageAnimation, &'a dyn Surface)> {
         match &mut self.image_animation {
             Some(animation) => Some((animation.as_mut(), self.image.as_ref())),
@@ -93,6 +148,38 @@ impl Sprite for DefaultSprite {
     fn is_killed(&self) -> bool {
         self.is_killed
     }
+
+    fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    fn set_angle(&mut self, angle: f32) {
+        self.angle = angle;
+        self.update_rectangle_size();
+    }
+
+    fn scale(&self) -> f32 {
+        self.scale
+    }
+
+    fn set_scale(&mut self, scale: f32) {
+        self.scale = scale;
+        self.update_rectangle_size();
+    }
+
+    fn layer(&self) -> i32 {
+        self.layer
+    }
+
+    fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    // the enclosing circle of the scaled image, which does not change with the rotation
+    fn radius(&self) -> f32 {
+        let (w, h) = self.image.get_size();
+        ((w * w + h * h) as f32).sqrt() / 2.0 * self.scale
+    }
 }
 
 pub trait RectAnimation {
@@ -107,36 +194,58 @@ pub trait SpriteGroup {
     fn sprites<'a>(&'a self) -> &'a Vec<Box<dyn Sprite>>;
     fn sprites_mut<'a>(&'a mut self) -> &'a mut Vec<Box<dyn Sprite>>;
 
-    fn update(&mut self) -> Result<(), Box<dyn Error>> {
-        for sprite in self.sprites_mut() {
-            let rectangle = match sprite.rectangle_animation() {
-                Some((rectangle_animation, rectangle)) => Some(rectangle_animation.update_rectangle(rectangle)),
-                _ => Option::None,
-            };
-            match rectangle {
-                Some(rectangle) => sprite.set_rect(rectangle),
-                _ => {}
-            }
-        }
-        for sprite in self.sprites_mut() {
-            sprite.update()?;
-        }
+    fn update(&mut self) -> Result<(), GameError> {
+        update_sprites(self.sprites_mut())?;
         self.sprites_mut().retain(|sprite| !sprite.is_killed());
         Ok(())
     }
 
-    fn draw(&mut self, surface: &mut dyn Surface) -> Result<(), Box<dyn Error>> {
-        for sprite in self.sprites_mut() {
-            let _ = match sprite.image_animation() {
-                Some((image_animation, image)) => {
-                    let animated_image = image_animation.transform_image(image);
-                    surface.blit(animated_image.as_ref(), sprite.rect().get_top_left(), BlendMode::Blend)
-                }
-                None => surface.blit(sprite.image(), sprite.rect().get_top_left(), BlendMode::Blend),
-            }?;
+    /// Draws the sprites and returns the rectangles which were changed on the surface.
+    fn draw(&mut self, surface: &mut dyn Surface) -> Result<Vec<Rect>, GameError> {
+        draw_sprites(self.sprites_mut(), surface)
+    }
+}
+
+fn update_sprites(sprites: &mut [Box<dyn Sprite>]) -> Result<(), GameError> {
+    for sprite in sprites.iter_mut() {
+        let rectangle = match sprite.rectangle_animation() {
+            Some((rectangle_animation, rectangle)) => Some(rectangle_animation.update_rectangle(rectangle)),
+            _ => Option::None,
+        };
+        match rectangle {
+            Some(rectangle) => sprite.set_rect(rectangle),
+            _ => {}
         }
-        Ok(())
     }
+    for sprite in sprites.iter_mut() {
+        sprite.update()?;
+    }
+    Ok(())
+}
+
+fn is_transformed(sprite: &dyn Sprite) -> bool {
+    sprite.angle() != 0.0 || sprite.scale() != 1.0
+}
+
+fn draw_sprites(sprites: &mut [Box<dyn Sprite>], surface: &mut dyn Surface) -> Result<Vec<Rect>, GameError> {
+    let mut rects = Vec::new();
+    for sprite in sprites.iter_mut() {
+        let animated_image = sprite.image_animation().map(|(image_animation, image)| image_animation.transform_image(image));
+        let image = match &animated_image {
+            Some(animated_image) => animated_image.as_ref(),
+            None => sprite.image(),
+        };
+        let rect = if is_transformed(sprite.as_ref()) {
+            let transformed_image = transform::rotozoom(image, sprite.angle(), sprite.scale())?;
+            let (w, h) = transformed_image.get_size();
+            let position = Rect::new_center(sprite.rect().get_center(), (w as i32, h as i32)).get_top_left();
+            surface.blit(transformed_image.as_ref(), position, BlendMode::Blend)
+        } else {
+            surface.blit(image, sprite.rect().get_top_left(), BlendMode::Blend)
+        }?;
+        rects.push(rect);
+    }
+    Ok(rects)
 }
 
 pub struct Group {
@@ -158,3 +267,277 @@ impl SpriteGroup for Group {
         &mut self.sprites
     }
 }
+
+/// Indices of the sprites in `group` which collide with `sprite`. Killed sprites are skipped.
+///
+/// With `kill`, the colliding sprites are killed. They are removed by the next `SpriteGroup::update`, so the indices stay valid until then.
+pub fn sprite_collide<F>(sprite: &dyn Sprite, group: &mut dyn SpriteGroup, kill: bool, predicate: F) -> Vec<usize>
+where
+    F: Fn(&dyn Sprite, &dyn Sprite) -> bool,
+{
+    let mut collisions = Vec::new();
+    for (i, other) in group.sprites_mut().iter_mut().enumerate() {
+        if !other.is_killed() && predicate(sprite, other.as_ref()) {
+            if kill {
+                other.kill();
+            }
+            collisions.push(i);
+        }
+    }
+    collisions
+}
+
+/// Index of the first sprite in `group` which collides with `sprite`.
+pub fn sprite_collide_any<F>(sprite: &dyn Sprite, group: &dyn SpriteGroup, predicate: F) -> Option<usize>
+where
+    F: Fn(&dyn Sprite, &dyn Sprite) -> bool,
+{
+    group.sprites().iter().position(|other| !other.is_killed() && predicate(sprite, other.as_ref()))
+}
+
+/// Index of each sprite in `group_a` which collides with sprites in `group_b`, together with the indices of these sprites.
+pub fn group_collide<F>(
+    group_a: &mut dyn SpriteGroup,
+    group_b: &mut dyn SpriteGroup,
+    kill_a: bool,
+    kill_b: bool,
+    predicate: F,
+) -> Vec<(usize, Vec<usize>)>
+where
+    F: Fn(&dyn Sprite, &dyn Sprite) -> bool,
+{
+    let mut collisions = Vec::new();
+    for (i, sprite) in group_a.sprites_mut().iter_mut().enumerate() {
+        if sprite.is_killed() {
+            continue;
+        }
+        let hits = sprite_collide(sprite.as_ref(), group_b, kill_b, &predicate);
+        if !hits.is_empty() {
+            if kill_a {
+                sprite.kill();
+            }
+            collisions.push((i, hits));
+        }
+    }
+    collisions
+}
+
+pub fn collide_rect(sprite_a: &dyn Sprite, sprite_b: &dyn Sprite) -> bool {
+    sprite_a.rect().collide_rect(sprite_b.rect())
+}
+
+/// Rectangle collision with both rectangles scaled by `ratio` around their centers.
+pub fn collide_rect_ratio(ratio: f32) -> impl Fn(&dyn Sprite, &dyn Sprite) -> bool {
+    move |sprite_a, sprite_b| sprite_a.rect().scale_by(ratio, ratio).collide_rect(&sprite_b.rect().scale_by(ratio, ratio))
+}
+
+fn sprite_circle(sprite: &dyn Sprite, ratio: f32) -> Circle {
+    Circle::new_center(sprite.rect().get_center(), (sprite.radius() * ratio).round() as i32)
+}
+
+/// Collision of the circles given by `Sprite::radius` around the centers of the sprites.
+pub fn collide_circle(sprite_a: &dyn Sprite, sprite_b: &dyn Sprite) -> bool {
+    sprite_circle(sprite_a, 1.0).collide_circle(&sprite_circle(sprite_b, 1.0))
+}
+
+pub fn collide_circle_ratio(ratio: f32) -> impl Fn(&dyn Sprite, &dyn Sprite) -> bool {
+    move |sprite_a, sprite_b| sprite_circle(sprite_a, ratio).collide_circle(&sprite_circle(sprite_b, ratio))
+}
+
+// mask from the alpha channel of the rotated and scaled image
+fn image_mask(sprite: &dyn Sprite) -> Option<Mask> {
+    if is_transformed(sprite) {
+        let image = transform::rotozoom(sprite.image(), sprite.angle(), sprite.scale()).ok()?;
+        Mask::from_surface(image.as_ref(), 127).ok()
+    } else {
+        Mask::from_surface(sprite.image(), 127).ok()
+    }
+}
+
+// mask of the sprite, or a mask of the image if the sprite has no mask
+fn sprite_mask(sprite: &dyn Sprite) -> Option<Cow<'_, Mask>> {
+    match sprite.mask() {
+        Some(mask) => Some(Cow::Borrowed(mask)),
+        None => image_mask(sprite).map(Cow::Owned),
+    }
+}
+
+/// Pixel-perfect collision of the sprite masks, placed at the top left of the sprite rectangles.
+pub fn collide_mask(sprite_a: &dyn Sprite, sprite_b: &dyn Sprite) -> bool {
+    let (rect_a, rect_b) = (sprite_a.rect(), sprite_b.rect());
+    if !rect_a.collide_rect(rect_b) {
+        return false;
+    }
+    match (sprite_mask(sprite_a), sprite_mask(sprite_b)) {
+        (Some(mask_a), Some(mask_b)) => mask_a.overlap(&mask_b, (rect_b.x - rect_a.x, rect_b.y - rect_a.y)).is_some(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod sprite_test {
+    use super::*;
+    use crate::color::ColorU8;
+    use crate::surface::software_surface::SoftwareSurface;
+    use crate::test::pixel;
+
+    fn sprite(center: (i32, i32), size: u32) -> Box<dyn Sprite> {
+        let mut image = SoftwareSurface::new((size, size));
+        image.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        DefaultSprite::new(Box::new(image), center)
+    }
+
+    // sprite with a single opaque pixel in the top left corner
+    fn dot_sprite(center: (i32, i32), size: u32) -> Box<dyn Sprite> {
+        let mut image = SoftwareSurface::new((size, size));
+        image.raw_mut().unwrap()[0..4].copy_from_slice(&[255, 0, 0, 255]);
+        DefaultSprite::new(Box::new(image), center)
+    }
+
+    #[test]
+    fn sprite_collide_test() {
+        let player = sprite((10, 10), 10);
+        let mut group = Group::new(vec![sprite((100, 100), 10), sprite((15, 15), 10), sprite((5, 10), 4)]);
+        assert_eq!(vec![1, 2], sprite_collide(player.as_ref(), group.as_mut(), false, collide_rect));
+        assert_eq!(3, group.sprites().len());
+        assert_eq!(vec![1, 2], sprite_collide(player.as_ref(), group.as_mut(), true, collide_rect));
+        assert!(group.sprites()[1].is_killed());
+        assert!(sprite_collide(player.as_ref(), group.as_mut(), false, collide_rect).is_empty());
+        group.update().unwrap();
+        assert_eq!(1, group.sprites().len());
+    }
+
+    #[test]
+    fn sprite_collide_any_test() {
+        let player = sprite((10, 10), 10);
+        let group = Group::new(vec![sprite((100, 100), 10), sprite((15, 15), 10), sprite((5, 10), 4)]);
+        assert_eq!(Some(1), sprite_collide_any(player.as_ref(), group.as_ref(), collide_rect));
+        let far_away = sprite((500, 500), 10);
+        assert_eq!(None, sprite_collide_any(far_away.as_ref(), group.as_ref(), collide_rect));
+    }
+
+    #[test]
+    fn group_collide_test() {
+        let mut shots = Group::new(vec![sprite((10, 10), 2), sprite((50, 50), 2), sprite((100, 100), 2)]);
+        let mut enemies = Group::new(vec![sprite((100, 100), 10), sprite((10, 10), 10), sprite((12, 12), 10)]);
+        let collisions = group_collide(shots.as_mut(), enemies.as_mut(), false, false, collide_rect);
+        assert_eq!(vec![(0, vec![1, 2]), (2, vec![0])], collisions);
+        let collisions = group_collide(shots.as_mut(), enemies.as_mut(), true, true, collide_rect);
+        assert_eq!(vec![(0, vec![1, 2]), (2, vec![0])], collisions);
+        shots.update().unwrap();
+        enemies.update().unwrap();
+        assert_eq!(1, shots.sprites().len());
+        assert_eq!(0, enemies.sprites().len());
+    }
+
+    #[test]
+    fn group_collide_kill_b_test() {
+        let mut shots = Group::new(vec![sprite((10, 10), 2), sprite((11, 11), 2)]);
+        let mut enemies = Group::new(vec![sprite((10, 10), 10)]);
+        let collisions = group_collide(shots.as_mut(), enemies.as_mut(), false, true, collide_rect);
+        assert_eq!(vec![(0, vec![0])], collisions);
+    }
+
+    #[test]
+    fn collide_rect_ratio_test() {
+        let a = sprite((10, 10), 10);
+        let b = sprite((18, 10), 10);
+        assert!(collide_rect(a.as_ref(), b.as_ref()));
+        assert!(!collide_rect_ratio(0.5)(a.as_ref(), b.as_ref()));
+        assert!(collide_rect_ratio(2.0)(a.as_ref(), sprite((25, 10), 10).as_ref()));
+    }
+
+    #[test]
+    fn collide_circle_test() {
+        let a = sprite((10, 10), 10);
+        assert!(collide_circle(a.as_ref(), sprite((22, 10), 10).as_ref()));
+        assert!(!collide_circle(a.as_ref(), sprite((25, 10), 10).as_ref()));
+        assert!(collide_circle(a.as_ref(), sprite((18, 18), 10).as_ref()));
+        assert!(!collide_circle_ratio(0.5)(a.as_ref(), sprite((18, 18), 10).as_ref()));
+    }
+
+    #[test]
+    fn collide_mask_test() {
+        let dot = dot_sprite((10, 10), 10);
+        assert!(collide_rect(dot.as_ref(), sprite((18, 18), 10).as_ref()));
+        assert!(!collide_mask(dot.as_ref(), sprite((18, 18), 10).as_ref()));
+        assert!(collide_mask(dot.as_ref(), sprite((8, 8), 10).as_ref()));
+        assert!(!collide_mask(dot.as_ref(), sprite((100, 100), 10).as_ref()));
+    }
+
+    #[test]
+    fn collide_mask_group_test() {
+        let dot = dot_sprite((10, 10), 10);
+        let mut group = Group::new(vec![sprite((18, 18), 10), sprite((8, 8), 10)]);
+        assert_eq!(vec![1], sprite_collide(dot.as_ref(), group.as_mut(), false, collide_mask));
+    }
+
+    #[test]
+    fn angle_scale_test() {
+        let mut image = SoftwareSurface::new((10, 4));
+        image.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        let mut sprite = DefaultSprite::new(Box::new(image), (20, 20));
+        assert_eq!((0.0, 1.0), (sprite.angle(), sprite.scale()));
+        sprite.set_angle(90.0);
+        assert_eq!(90.0, sprite.angle());
+        assert_eq!((18, 15, 4, 10), (sprite.rect().x, sprite.rect().y, sprite.rect().w, sprite.rect().h));
+        sprite.set_scale(2.0);
+        assert_eq!(2.0, sprite.scale());
+        assert_eq!((16, 10, 8, 20), (sprite.rect().x, sprite.rect().y, sprite.rect().w, sprite.rect().h));
+        sprite.set_angle(0.0);
+        sprite.set_scale(1.0);
+        assert_eq!((15, 18, 10, 4), (sprite.rect().x, sprite.rect().y, sprite.rect().w, sprite.rect().h));
+    }
+
+    #[test]
+    fn radius_circle_test() {
+        let mut sprite = sprite((10, 10), 6);
+        assert!((sprite.radius() - 18f32.sqrt()).abs() < 1e-5);
+        sprite.set_angle(45.0);
+        assert!((sprite.radius() - 18f32.sqrt()).abs() < 1e-5);
+        sprite.set_scale(2.0);
+        let circle = sprite.circle();
+        assert_eq!((10, 10, 8), (circle.x, circle.y, circle.r));
+    }
+
+    #[test]
+    fn draw_transformed_test() {
+        let mut image = SoftwareSurface::new((4, 2));
+        image.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        let mut sprite = DefaultSprite::new(Box::new(image), (5, 5));
+        sprite.set_angle(90.0);
+        let mut group = Group::new(vec![sprite]);
+        let mut surface = SoftwareSurface::new((10, 10));
+        let rects = group.draw(&mut surface).unwrap();
+        assert_eq!((4, 3, 2, 4), (rects[0].x, rects[0].y, rects[0].w, rects[0].h));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 4, 3));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 5, 6));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 3, 5));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 6, 5));
+    }
+
+    #[test]
+    fn collide_mask_transformed_test() {
+        let mut image = SoftwareSurface::new((10, 2));
+        image.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        let mut bar = DefaultSprite::new(Box::new(image), (10, 10));
+        let dot = dot_sprite((10, 7), 2);
+        assert!(!collide_mask(bar.as_ref(), dot.as_ref()));
+        bar.set_angle(90.0);
+        assert!(collide_mask(bar.as_ref(), dot.as_ref()));
+    }
+
+    #[test]
+    fn mask_cache_test() {
+        let mut image = SoftwareSurface::new((10, 2));
+        image.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        let mut bar = DefaultSprite::new(Box::new(image), (10, 10));
+        let mask = bar.mask().unwrap() as *const Mask;
+        assert!(std::ptr::eq(mask, bar.mask().unwrap()));
+        assert_eq!((10, 2), bar.mask().unwrap().get_size());
+        bar.set_angle(90.0);
+        assert_eq!((2, 10), bar.mask().unwrap().get_size());
+        bar.set_scale(2.0);
+        assert_eq!((4, 20), bar.mask().unwrap().get_size());
+    }
+}
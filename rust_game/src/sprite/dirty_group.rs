@@ -0,0 +1,174 @@
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::sprite::{draw_sprites, Sprite, SpriteGroup};
+use crate::surface::{BlendMode, Surface};
+
+/// Sprite group for dirty-rectangle rendering.
+///
+/// `draw` restores the background where the sprites were drawn the last time, draws the sprites and returns the changed rectangles,
+/// which can be passed to `Canvas::update_rects`. The background has to be blitted to the surface once before the first `draw`.
+pub struct DirtyGroup {
+    sprites: Vec<Box<dyn Sprite>>,
+    background: Box<dyn Surface>,
+    previous_rects: Vec<Rect>,
+}
+
+impl DirtyGroup {
+    pub fn new(sprites: Vec<Box<dyn Sprite>>, background: Box<dyn Surface>) -> DirtyGroup {
+        DirtyGroup {
+            sprites,
+            background,
+            previous_rects: Vec::new(),
+        }
+    }
+
+    /// The new background is used to clear the rectangles of the next `draw`.
+    pub fn set_background(&mut self, background: Box<dyn Surface>) {
+        self.background = background;
+    }
+
+    pub fn background(&self) -> &dyn Surface {
+        self.background.as_ref()
+    }
+
+    // blits the background clipped to the rectangle, so only the region is copied to the surface
+    fn clear_rect(&self, surface: &mut dyn Surface, rect: &Rect) -> Result<Rect, GameError> {
+        let clip = surface.get_clip();
+        surface.set_clip(Some(clip.clip(rect)))?;
+        let cleared_rect = surface.blit(self.background.as_ref(), (0, 0), BlendMode::None);
+        surface.set_clip(Some(clip))?;
+        cleared_rect
+    }
+}
+
+// adds the rectangle to the list, overlapping rectangles are joined
+fn add_dirty_rect(dirty_rects: &mut Vec<Rect>, rect: Rect) {
+    if rect.w <= 0 || rect.h <= 0 {
+        return;
+    }
+    match dirty_rects.iter_mut().find(|dirty_rect| dirty_rect.collide_rect(&rect)) {
+        Some(dirty_rect) => dirty_rect.union_ip(&rect),
+        None => dirty_rects.push(rect),
+    }
+}
+
+impl SpriteGroup for DirtyGroup {
+    fn sprites(&self) -> &Vec<Box<dyn Sprite>> {
+        &self.sprites
+    }
+
+    fn sprites_mut(&mut self) -> &mut Vec<Box<dyn Sprite>> {
+        &mut self.sprites
+    }
+
+    fn draw(&mut self, surface: &mut dyn Surface) -> Result<Vec<Rect>, GameError> {
+        let mut dirty_rects = Vec::new();
+        for rect in std::mem::take(&mut self.previous_rects) {
+            add_dirty_rect(&mut dirty_rects, self.clear_rect(surface, &rect)?);
+        }
+        self.previous_rects = draw_sprites(&mut self.sprites, surface)?;
+        for rect in self.previous_rects.iter() {
+            add_dirty_rect(&mut dirty_rects, rect.clone());
+        }
+        Ok(dirty_rects)
+    }
+}
+
+#[cfg(test)]
+mod dirty_group_test {
+    use super::*;
+    use crate::color::ColorU8;
+    use crate::sprite::DefaultSprite;
+    use crate::surface::software_surface::SoftwareSurface;
+    use crate::test::pixel;
+
+    fn rect_tuple(rect: &Rect) -> (i32, i32, i32, i32) {
+        (rect.x, rect.y, rect.w, rect.h)
+    }
+
+    fn setup() -> (DirtyGroup, SoftwareSurface) {
+        let mut background = SoftwareSurface::new((10, 10));
+        background.fill(&ColorU8::new_rgb(0, 0, 255)).unwrap();
+        let mut image = SoftwareSurface::new((2, 2));
+        image.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        let mut surface = SoftwareSurface::new((10, 10));
+        surface.blit(&background, (0, 0), BlendMode::None).unwrap();
+        let group = DirtyGroup::new(vec![DefaultSprite::new(Box::new(image), (2, 2))], Box::new(background));
+        (group, surface)
+    }
+
+    #[test]
+    fn draw_test() {
+        let (mut group, mut surface) = setup();
+        let rects = group.draw(&mut surface).unwrap();
+        assert_eq!(vec![(1, 1, 2, 2)], rects.iter().map(rect_tuple).collect::<Vec<_>>());
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 1, 1));
+
+        group.sprites_mut()[0].set_rect(Rect::new(6, 6, 2, 2));
+        let rects = group.draw(&mut surface).unwrap();
+        assert_eq!(vec![(1, 1, 2, 2), (6, 6, 2, 2)], rects.iter().map(rect_tuple).collect::<Vec<_>>());
+        assert_eq!([0, 0, 255, 255], pixel(&surface, 1, 1));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 6, 6));
+    }
+
+    #[test]
+    fn draw_overlapping_test() {
+        let (mut group, mut surface) = setup();
+        group.draw(&mut surface).unwrap();
+        group.sprites_mut()[0].set_rect(Rect::new(2, 1, 2, 2));
+        let rects = group.draw(&mut surface).unwrap();
+        assert_eq!(vec![(1, 1, 3, 2)], rects.iter().map(rect_tuple).collect::<Vec<_>>());
+        assert_eq!([0, 0, 255, 255], pixel(&surface, 1, 1));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 2, 1));
+    }
+
+    #[test]
+    fn draw_killed_test() {
+        let (mut group, mut surface) = setup();
+        group.draw(&mut surface).unwrap();
+        group.sprites_mut()[0].kill();
+        group.update().unwrap();
+        let rects = group.draw(&mut surface).unwrap();
+        assert_eq!(vec![(1, 1, 2, 2)], rects.iter().map(rect_tuple).collect::<Vec<_>>());
+        assert_eq!([0, 0, 255, 255], pixel(&surface, 2, 2));
+        assert!(group.draw(&mut surface).unwrap().is_empty());
+    }
+
+    #[test]
+    fn draw_outside_test() {
+        let (mut group, mut surface) = setup();
+        group.sprites_mut()[0].set_rect(Rect::new(-1, 9, 2, 2));
+        let rects = group.draw(&mut surface).unwrap();
+        assert_eq!(vec![(0, 9, 1, 1)], rects.iter().map(rect_tuple).collect::<Vec<_>>());
+        group.sprites_mut()[0].set_rect(Rect::new(20, 20, 2, 2));
+        let rects = group.draw(&mut surface).unwrap();
+        assert_eq!(vec![(0, 9, 1, 1)], rects.iter().map(rect_tuple).collect::<Vec<_>>());
+        assert_eq!([0, 0, 255, 255], pixel(&surface, 0, 9));
+    }
+
+    #[test]
+    fn draw_clip_test() {
+        let (mut group, mut surface) = setup();
+        group.draw(&mut surface).unwrap();
+        surface.set_clip(Some(Rect::new(0, 0, 2, 10))).unwrap();
+        group.sprites_mut()[0].set_rect(Rect::new(6, 6, 2, 2));
+        let rects = group.draw(&mut surface).unwrap();
+        assert_eq!(vec![(1, 1, 1, 2)], rects.iter().map(rect_tuple).collect::<Vec<_>>());
+        assert_eq!([0, 0, 255, 255], pixel(&surface, 1, 1));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 2, 1));
+        assert_eq!((0, 0, 2, 10), rect_tuple(&surface.get_clip()));
+    }
+
+    #[test]
+    fn set_background_test() {
+        let (mut group, mut surface) = setup();
+        group.draw(&mut surface).unwrap();
+        let mut background = SoftwareSurface::new((10, 10));
+        background.fill(&ColorU8::new_rgb(0, 255, 0)).unwrap();
+        group.set_background(Box::new(background));
+        group.sprites_mut()[0].set_rect(Rect::new(6, 6, 2, 2));
+        group.draw(&mut surface).unwrap();
+        assert_eq!([0, 255, 0, 255], pixel(&surface, 1, 1));
+        assert_eq!((10, 10), group.background().get_size());
+    }
+}
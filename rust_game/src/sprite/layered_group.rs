@@ -0,0 +1,284 @@
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::sprite::{draw_sprites, Sprite, SpriteGroup};
+use crate::surface::Surface;
+
+pub const DEFAULT_LAYER: i32 = 0;
+
+/// Sprite group which draws the sprites sorted by layer, from the bottom layer to the top layer.
+///
+/// The layer is stored in the sprite with `Sprite::set_layer`, so it moves with the sprite.
+/// The sprites are kept sorted by layer, sprites in the same layer keep the order in which they were added.
+/// Indices refer to `sprites()` and change when sprites are added, removed or moved to another layer.
+/// Sprites can be added, removed or reordered with `sprites_mut()`, the order by layer is restored by the next `add` or `draw`.
+pub struct LayeredGroup {
+    sprites: Vec<Box<dyn Sprite>>,
+}
+
+impl LayeredGroup {
+    pub fn new(sprites: Vec<(Box<dyn Sprite>, i32)>) -> LayeredGroup {
+        let mut group = LayeredGroup { sprites: Vec::new() };
+        for (sprite, layer) in sprites {
+            group.add(sprite, layer);
+        }
+        group
+    }
+
+    /// Adds the sprite on top of the sprites in its layer and returns its index.
+    pub fn add(&mut self, mut sprite: Box<dyn Sprite>, layer: i32) -> usize {
+        sprite.set_layer(layer);
+        self.sort();
+        let index = self.sprites.partition_point(|other| other.layer() <= sprite.layer());
+        self.sprites.insert(index, sprite);
+        index
+    }
+
+    pub fn remove(&mut self, index: usize) -> Box<dyn Sprite> {
+        self.sprites.remove(index)
+    }
+
+    pub fn get_layer(&self, index: usize) -> i32 {
+        self.sprites[index].layer()
+    }
+
+    /// Distinct layers in ascending order.
+    pub fn layers(&self) -> Vec<i32> {
+        let mut layers: Vec<i32> = self.sprites.iter().map(|sprite| sprite.layer()).collect();
+        layers.sort_unstable();
+        layers.dedup();
+        layers
+    }
+
+    pub fn get_top_layer(&self) -> Option<i32> {
+        self.sprites.iter().map(|sprite| sprite.layer()).max()
+    }
+
+    pub fn get_bottom_layer(&self) -> Option<i32> {
+        self.sprites.iter().map(|sprite| sprite.layer()).min()
+    }
+
+    /// Indices of the sprites in the layer, from bottom to top.
+    pub fn get_sprites_from_layer(&self, layer: i32) -> Vec<usize> {
+        (0..self.sprites.len()).filter(|&i| self.get_layer(i) == layer).collect()
+    }
+
+    /// Moves the sprite on top of the sprites in `layer` and returns its new index.
+    pub fn change_layer(&mut self, index: usize, layer: i32) -> usize {
+        let sprite = self.remove(index);
+        self.add(sprite, layer)
+    }
+
+    /// Moves the sprite to the top layer, on top of all other sprites, and returns its new index.
+    pub fn move_to_front(&mut self, index: usize) -> usize {
+        let layer = self.get_top_layer().unwrap_or(DEFAULT_LAYER);
+        self.change_layer(index, layer)
+    }
+
+    /// Moves the sprite to a new layer below the bottom layer and returns its new index, which is 0.
+    pub fn move_to_back(&mut self, index: usize) -> usize {
+        let layer = self.get_bottom_layer().unwrap_or(DEFAULT_LAYER);
+        self.change_layer(index, layer - 1)
+    }
+
+    /// Indices of the sprites whose rectangle contains the point, from bottom to top.
+    pub fn get_sprites_at(&self, point: (i32, i32)) -> Vec<usize> {
+        (0..self.sprites.len()).filter(|&i| self.sprites[i].rect().collide_point(point.0, point.1)).collect()
+    }
+
+    /// Index of the sprite which is drawn last.
+    pub fn get_top_sprite(&self) -> Option<usize> {
+        self.sprites.len().checked_sub(1)
+    }
+
+    // restores the order if sprites were reordered with `sprites_mut()` or changed their layer, the sort is stable
+    fn sort(&mut self) {
+        self.sprites.sort_by_key(|sprite| sprite.layer());
+    }
+}
+
+impl SpriteGroup for LayeredGroup {
+    fn sprites(&self) -> &Vec<Box<dyn Sprite>> {
+        &self.sprites
+    }
+
+    fn sprites_mut(&mut self) -> &mut Vec<Box<dyn Sprite>> {
+        &mut self.sprites
+    }
+
+    fn draw(&mut self, surface: &mut dyn Surface) -> Result<Vec<Rect>, GameError> {
+        self.sort();
+        draw_sprites(&mut self.sprites, surface)
+    }
+}
+
+#[cfg(test)]
+mod layered_group_test {
+    use super::*;
+    use crate::color::ColorU8;
+    use crate::sprite::DefaultSprite;
+    use crate::surface::software_surface::SoftwareSurface;
+    use crate::test::pixel;
+
+    fn sprite(center: (i32, i32), color: u8) -> Box<dyn Sprite> {
+        let mut image = SoftwareSurface::new((2, 2));
+        image.fill(&ColorU8::new_rgb(color, 0, 0)).unwrap();
+        DefaultSprite::new(Box::new(image), center)
+    }
+
+    // minimal sprite which stores only what the trait requires
+    struct LayerSprite {
+        image: SoftwareSurface,
+        rect: Rect,
+        layer: i32,
+    }
+
+    impl Sprite for LayerSprite {
+        fn image(&self) -> &dyn Surface {
+            &self.image
+        }
+
+        fn rect(&self) -> &Rect {
+            &self.rect
+        }
+
+        fn set_rect(&mut self, rect: Rect) {
+            self.rect = rect;
+        }
+
+        fn layer(&self) -> i32 {
+            self.layer
+        }
+
+        fn set_layer(&mut self, layer: i32) {
+            self.layer = layer;
+        }
+    }
+
+    fn colors(group: &LayeredGroup) -> Vec<u8> {
+        group.sprites().iter().map(|sprite| sprite.image().raw().unwrap()[0]).collect()
+    }
+
+    #[test]
+    fn new_test() {
+        let group = LayeredGroup::new(vec![
+            (sprite((1, 1), 1), 2),
+            (sprite((1, 1), 2), 0),
+            (sprite((1, 1), 3), 2),
+            (sprite((1, 1), 4), 1),
+        ]);
+        assert_eq!(vec![2, 4, 1, 3], colors(&group));
+        assert_eq!(vec![0, 1, 2], group.layers());
+        assert_eq!(Some(2), group.get_top_layer());
+        assert_eq!(Some(0), group.get_bottom_layer());
+    }
+
+    #[test]
+    fn add_remove_test() {
+        let mut group = LayeredGroup::new(vec![(sprite((1, 1), 1), 0), (sprite((1, 1), 2), 2)]);
+        assert_eq!(1, group.add(sprite((1, 1), 3), 1));
+        assert_eq!(1, group.add(sprite((1, 1), 4), 0));
+        assert_eq!(vec![1, 4, 3, 2], colors(&group));
+        assert_eq!(vec![0, 0, 1, 2], (0..4).map(|i| group.get_layer(i)).collect::<Vec<i32>>());
+        assert_eq!(4, group.remove(1).image().raw().unwrap()[0]);
+        assert_eq!(vec![1, 3, 2], colors(&group));
+        assert_eq!(vec![1], group.get_sprites_from_layer(1));
+    }
+
+    #[test]
+    fn change_layer_test() {
+        let mut group = LayeredGroup::new(vec![(sprite((1, 1), 1), 0), (sprite((1, 1), 2), 1), (sprite((1, 1), 3), 1)]);
+        assert_eq!(2, group.change_layer(0, 1));
+        assert_eq!(vec![2, 3, 1], colors(&group));
+        assert_eq!(0, group.change_layer(2, -5));
+        assert_eq!(vec![1, 2, 3], colors(&group));
+        assert_eq!(-5, group.get_layer(0));
+    }
+
+    #[test]
+    fn move_to_front_back_test() {
+        let mut group = LayeredGroup::new(vec![(sprite((1, 1), 1), 0), (sprite((1, 1), 2), 1), (sprite((1, 1), 3), 2)]);
+        assert_eq!(2, group.move_to_front(0));
+        assert_eq!(vec![2, 3, 1], colors(&group));
+        assert_eq!(2, group.get_layer(2));
+        assert_eq!(0, group.move_to_back(2));
+        assert_eq!(vec![1, 2, 3], colors(&group));
+        assert_eq!(0, group.get_layer(0));
+        assert_eq!(0, group.move_to_back(1));
+        assert_eq!(vec![2, 1, 3], colors(&group));
+        assert_eq!(-1, group.get_layer(0));
+    }
+
+    #[test]
+    fn get_sprites_at_test() {
+        let group = LayeredGroup::new(vec![(sprite((5, 5), 1), 1), (sprite((5, 5), 2), 0), (sprite((20, 20), 3), 0)]);
+        assert_eq!(vec![0, 2], group.get_sprites_at((5, 5)));
+        assert_eq!(vec![1], group.get_sprites_at((20, 20)));
+        assert!(group.get_sprites_at((50, 50)).is_empty());
+        assert_eq!(Some(2), group.get_top_sprite());
+        assert_eq!(None, LayeredGroup::new(vec![]).get_top_sprite());
+    }
+
+    #[test]
+    fn draw_test() {
+        let mut group = LayeredGroup::new(vec![(sprite((1, 1), 10), 1), (sprite((1, 1), 20), 0), (sprite((2, 2), 30), 0)]);
+        let mut surface = SoftwareSurface::new((4, 4));
+        group.draw(&mut surface).unwrap();
+        assert_eq!([10, 0, 0, 255], pixel(&surface, 1, 1));
+        assert_eq!([10, 0, 0, 255], pixel(&surface, 0, 0));
+        assert_eq!([30, 0, 0, 255], pixel(&surface, 2, 2));
+    }
+
+    #[test]
+    fn sprites_mut_test() {
+        let mut group = LayeredGroup::new(vec![(sprite((1, 1), 1), 1), (sprite((1, 1), 2), -1)]);
+        group.sprites_mut().push(sprite((1, 1), 3));
+        let mut surface = SoftwareSurface::new((4, 4));
+        group.draw(&mut surface).unwrap();
+        assert_eq!(vec![2, 3, 1], colors(&group));
+        assert_eq!(DEFAULT_LAYER, group.get_layer(1));
+    }
+
+    #[test]
+    fn sprites_mut_structural_test() {
+        let mut group = LayeredGroup::new(vec![(sprite((1, 1), 1), 0), (sprite((1, 1), 2), 1), (sprite((1, 1), 3), 2)]);
+        group.sprites_mut().remove(0);
+        assert_eq!(vec![1, 2], (0..2).map(|i| group.get_layer(i)).collect::<Vec<i32>>());
+        group.sprites_mut().swap(0, 1);
+        group.sprites_mut().insert(1, sprite((1, 1), 4));
+        group.sprites_mut().retain(|sprite| sprite.image().raw().unwrap()[0] != 3);
+        assert_eq!(vec![4, 2], colors(&group));
+        assert_eq!(2, group.add(sprite((1, 1), 5), 1));
+        assert_eq!(vec![4, 2, 5], colors(&group));
+        assert_eq!(vec![0, 1, 1], (0..3).map(|i| group.get_layer(i)).collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn custom_sprite_test() {
+        let mut image = SoftwareSurface::new((2, 2));
+        image.fill(&ColorU8::new_rgb(5, 0, 0)).unwrap();
+        let custom = Box::new(LayerSprite {
+            image,
+            rect: Rect::new(0, 0, 2, 2),
+            layer: DEFAULT_LAYER,
+        });
+        let mut group = LayeredGroup::new(vec![(sprite((1, 1), 1), 0), (sprite((1, 1), 2), 2)]);
+        assert_eq!(1, group.add(custom, 1));
+        assert_eq!(vec![0, 1, 2], group.layers());
+        assert_eq!(1, group.get_layer(1));
+        assert_eq!(2, group.change_layer(1, 5));
+        assert_eq!(vec![1, 2, 5], colors(&group));
+        assert_eq!(Some(5), group.get_top_layer());
+        assert_eq!(0, group.move_to_back(2));
+        assert_eq!(-1, group.get_layer(0));
+        assert_eq!(vec![5, 1, 2], colors(&group));
+    }
+
+    #[test]
+    fn update_test() {
+        let mut group = LayeredGroup::new(vec![(sprite((1, 1), 1), 0), (sprite((1, 1), 2), 1), (sprite((1, 1), 3), 2)]);
+        group.sprites_mut()[1].kill();
+        group.update().unwrap();
+        assert_eq!(vec![1, 3], colors(&group));
+        assert_eq!(vec![0, 2], group.layers());
+    }
+}
@@ -1,8 +1,21 @@
-use crate::color::Color;
+pub mod pixel_view;
+pub mod software_surface;
+pub mod sub_surface;
+
+use crate::color::{Color, ColorU8};
+use crate::error::GameError;
 use crate::rectangle::Rect;
+use crate::surface::pixel_view::{out_of_bounds, PixelView, PixelViewMut};
+use crate::surface::sub_surface::SubSurface;
 use std::any::Any;
-use std::error::Error;
 
+/// How the pixels of the source are combined with the pixels of the destination by `Surface::blit`.
+///
+/// `None` to `Multiply` are the SDL2 surface blend modes and `MultiplyRGBA` multiplies all 4 channels.
+/// `Screen`, `Overlay`, `Darken`, `Lighten` and `Difference` compose the result of the mode with the destination like `Blend`,
+/// `Subtract` is the counterpart of `Add`, `Min` and `Max` take the minimum and maximum of all 4 channels and
+/// `PremultipliedBlend` expects a source with premultiplied alpha.
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[repr(i32)]
 pub enum BlendMode {
     None,
@@ -11,26 +24,146 @@ pub enum BlendMode {
     Modulate,
     Multiply,
     MultiplyRGBA,
-    Invalid,
+    Screen,
+    Overlay,
+    Subtract,
+    Min,
+    Max,
+    Darken,
+    Lighten,
+    Difference,
+    PremultipliedBlend,
+}
+
+impl TryFrom<i32> for BlendMode {
+    type Error = GameError;
+
+    fn try_from(value: i32) -> Result<BlendMode, GameError> {
+        let blend_mode = match value {
+            0 => BlendMode::None,
+            1 => BlendMode::Blend,
+            2 => BlendMode::Add,
+            3 => BlendMode::Modulate,
+            4 => BlendMode::Multiply,
+            5 => BlendMode::MultiplyRGBA,
+            6 => BlendMode::Screen,
+            7 => BlendMode::Overlay,
+            8 => BlendMode::Subtract,
+            9 => BlendMode::Min,
+            10 => BlendMode::Max,
+            11 => BlendMode::Darken,
+            12 => BlendMode::Lighten,
+            13 => BlendMode::Difference,
+            14 => BlendMode::PremultipliedBlend,
+            _ => Err(GameError::InvalidBlendMode(value))?,
+        };
+        Ok(blend_mode)
+    }
+}
+
+/// Byte order of the pixels in the raw data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PixelFormat {
+    RGBA8,
 }
 
 pub trait Surface {
     fn as_any<'a>(&'a self) -> &'a dyn Any;
-    fn clone(&self) -> Result<Box<dyn Surface>, Box<dyn Error>>;
-    fn modulate_surface_and_color(&self, color: &dyn Color) -> Result<Box<dyn Surface>, Box<dyn Error>>;
+    fn clone(&self) -> Result<Box<dyn Surface>, GameError>;
+    fn modulate_surface_and_color(&self, color: &dyn Color) -> Result<Box<dyn Surface>, GameError>;
     fn get_width(&self) -> u32;
     fn get_height(&self) -> u32;
     fn get_size(&self) -> (u32, u32);
     fn get_rect(&self) -> Rect;
-    fn raw(&self) -> Result<&[u8], Box<dyn Error>>;
-    fn raw_mut(&mut self) -> Result<&mut [u8], Box<dyn Error>>;
-    fn fill(&mut self, color: &dyn Color) -> Result<(), Box<dyn Error>>;
-    fn blit(&mut self, source_surface: &dyn Surface, position: (i32, i32), blend_mode: BlendMode) -> Result<Rect, Box<dyn Error>>;
+    /// RGBA8 data of the pixels, the rows start `get_pitch()` bytes apart.
+    fn raw(&self) -> Result<&[u8], GameError>;
+    fn raw_mut(&mut self) -> Result<&mut [u8], GameError>;
+
+    /// Number of bytes from the start of one row of the raw data to the start of the next row.
+    fn get_pitch(&self) -> usize {
+        self.get_width() as usize * 4
+    }
+
+    fn get_format(&self) -> PixelFormat {
+        PixelFormat::RGBA8
+    }
+
+    fn pixels(&self) -> Result<PixelView<'_>, GameError> {
+        PixelView::new(self.raw()?, self.get_size(), self.get_pitch())
+    }
+
+    fn pixels_mut(&mut self) -> Result<PixelViewMut<'_>, GameError> {
+        let (size, pitch) = (self.get_size(), self.get_pitch());
+        PixelViewMut::new(self.raw_mut()?, size, pitch)
+    }
+
+    /// Color of the pixel, an error is returned if the position is outside of the surface.
+    fn get_at(&self, position: (i32, i32)) -> Result<ColorU8, GameError> {
+        match self.pixels()?.get(position.0, position.1) {
+            Some(color) => Ok(*color),
+            None => Err(out_of_bounds(position, self.get_size())),
+        }
+    }
+
+    /// Sets the color of the pixel without blending, an error is returned if the position is outside of the surface.
+    fn set_at(&mut self, position: (i32, i32), color: &dyn Color) -> Result<(), GameError> {
+        match self.pixels_mut()?.set(position.0, position.1, color) {
+            true => Ok(()),
+            false => Err(out_of_bounds(position, self.get_size())),
+        }
+    }
+
+    fn get_row(&self, y: i32) -> Result<&[ColorU8], GameError> {
+        match self.pixels()?.row(y) {
+            Some(row) => Ok(row),
+            None => Err(out_of_bounds((0, y), self.get_size())),
+        }
+    }
+
+    /// View of the region `rect`, which reads and writes the pixels of this surface.
+    ///
+    /// The coordinates on the view are relative to the top left of `rect`, which has to lie inside of the surface.
+    fn subsurface(&mut self, rect: &Rect) -> Result<Box<dyn Surface + '_>, GameError> {
+        let (parent_rect, pitch) = (self.get_rect(), self.get_pitch());
+        Ok(Box::new(SubSurface::new(self.raw_mut()?, pitch, &parent_rect, rect)?))
+    }
+
+    fn fill(&mut self, color: &dyn Color) -> Result<(), GameError>;
+
+    /// Pixels whose RGB matches the color key are not drawn when the surface is blitted.
+    fn set_colorkey(&mut self, _color_key: Option<&dyn Color>) -> Result<(), GameError> {
+        Err(GameError::Unsupported("color key".to_string()))?
+    }
+
+    fn get_colorkey(&self) -> Option<ColorU8> {
+        Option::None
+    }
+
+    /// The alpha of the pixels is multiplied by the surface alpha when the surface is blitted.
+    fn set_alpha(&mut self, _alpha: u8) -> Result<(), GameError> {
+        Err(GameError::Unsupported("surface alpha".to_string()))?
+    }
+
+    fn get_alpha(&self) -> u8 {
+        255
+    }
+
+    /// Restricts `blit`, `fill` and the `Draw` primitives to the rectangle, `None` removes the restriction.
+    fn set_clip(&mut self, _clip: Option<Rect>) -> Result<(), GameError> {
+        Err(GameError::Unsupported("clip rectangle".to_string()))?
+    }
+
+    /// The clip rectangle clipped to the surface, or the rectangle of the surface if there is no clip rectangle.
+    fn get_clip(&self) -> Rect {
+        self.get_rect()
+    }
+
+    fn blit(&mut self, source_surface: &dyn Surface, position: (i32, i32), blend_mode: BlendMode) -> Result<Rect, GameError>;
 }
 
 pub trait SurfaceBuilder {
-    fn new_surface_alpha(size: (u32, u32)) -> Result<Box<dyn Surface>, Box<dyn Error>>;
-    fn new_surface_with_color(size: (u32, u32), color: &dyn Color) -> Result<Box<dyn Surface>, Box<dyn Error>>;
+    fn new_surface_alpha(size: (u32, u32)) -> Result<Box<dyn Surface>, GameError>;
+    fn new_surface_with_color(size: (u32, u32), color: &dyn Color) -> Result<Box<dyn Surface>, GameError>;
 }
 
 #[cfg(test)]
@@ -39,6 +172,14 @@ mod test_surface {
     use crate::color::ColorU8;
     use crate::test::SurfaceMock;
 
+    #[test]
+    fn test_blend_mode_try_from() {
+        assert_eq!(BlendMode::None, BlendMode::try_from(0).unwrap());
+        assert_eq!(BlendMode::Difference, BlendMode::try_from(BlendMode::Difference as i32).unwrap());
+        assert_eq!(15, (0..).map_while(|value| BlendMode::try_from(value).ok()).count());
+        assert!(matches!(BlendMode::try_from(-1), Err(GameError::InvalidBlendMode(-1))));
+    }
+
     #[test]
     fn test_fill() {
         let mut surface = SurfaceMock::new();
@@ -0,0 +1,277 @@
+use crate::color::{Color, ColorU8};
+use crate::error::GameError;
+use crate::surface::PixelFormat;
+use std::ops::{Index, IndexMut};
+
+// byte offset of the pixel in the raw data, `None` if the pixel is outside of the surface
+fn pixel_offset(size: (u32, u32), pitch: usize, x: i32, y: i32) -> Option<usize> {
+    match x >= 0 && y >= 0 && (x as u32) < size.0 && (y as u32) < size.1 {
+        true => Some(y as usize * pitch + x as usize * 4),
+        false => Option::None,
+    }
+}
+
+fn check_raw_data(len: usize, size: (u32, u32), pitch: usize) -> Result<(), GameError> {
+    let row_len = size.0 as usize * 4;
+    if pitch < row_len || (size.1 > 0 && len < (size.1 as usize - 1) * pitch + row_len) {
+        Err(GameError::InvalidGeometry("raw data does not match the surface size".to_string()))?
+    }
+    Ok(())
+}
+
+pub(crate) fn out_of_bounds(position: (i32, i32), size: (u32, u32)) -> GameError {
+    GameError::InvalidGeometry(format!("pixel ({}, {}) is outside of the {}x{} surface", position.0, position.1, size.0, size.1))
+}
+
+// `ColorU8` is a transparent wrapper of `[u8; 4]`, so RGBA8 bytes can be reinterpreted as colors
+fn bytes_to_colors(bytes: &[u8]) -> &[ColorU8] {
+    let (pixels, _) = bytes.as_chunks::<4>();
+    unsafe { std::slice::from_raw_parts(pixels.as_ptr().cast::<ColorU8>(), pixels.len()) }
+}
+
+fn bytes_to_colors_mut(bytes: &mut [u8]) -> &mut [ColorU8] {
+    let (pixels, _) = bytes.as_chunks_mut::<4>();
+    unsafe { std::slice::from_raw_parts_mut(pixels.as_mut_ptr().cast::<ColorU8>(), pixels.len()) }
+}
+
+/// Read-only view of the pixels of a surface, see `Surface::pixels`.
+///
+/// The pixels are addressed by `(x, y)`, `get` and `row` return `None` outside of the surface and indexing panics.
+pub struct PixelView<'a> {
+    data: &'a [u8],
+    size: (u32, u32),
+    pitch: usize,
+}
+
+impl<'a> PixelView<'a> {
+    pub fn new(data: &'a [u8], size: (u32, u32), pitch: usize) -> Result<PixelView<'a>, GameError> {
+        check_raw_data(data.len(), size, pitch)?;
+        Ok(PixelView { data, size, pitch })
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.size.0
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.size.1
+    }
+
+    pub fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    pub fn get_pitch(&self) -> usize {
+        self.pitch
+    }
+
+    pub fn get_format(&self) -> PixelFormat {
+        PixelFormat::RGBA8
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<&'a ColorU8> {
+        let offset = pixel_offset(self.size, self.pitch, x, y)?;
+        Some(&bytes_to_colors(&self.data[offset..offset + 4])[0])
+    }
+
+    pub fn row(&self, y: i32) -> Option<&'a [ColorU8]> {
+        let offset = pixel_offset(self.size, self.pitch, 0, y)?;
+        Some(bytes_to_colors(&self.data[offset..offset + self.size.0 as usize * 4]))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [ColorU8]> + '_ {
+        (0..self.size.1 as i32).filter_map(|y| self.row(y))
+    }
+}
+
+impl Index<(i32, i32)> for PixelView<'_> {
+    type Output = ColorU8;
+
+    fn index(&self, position: (i32, i32)) -> &ColorU8 {
+        match self.get(position.0, position.1) {
+            Some(color) => color,
+            None => panic!("{}", out_of_bounds(position, self.size)),
+        }
+    }
+}
+
+/// Mutable view of the pixels of a surface, see `Surface::pixels_mut`.
+pub struct PixelViewMut<'a> {
+    data: &'a mut [u8],
+    size: (u32, u32),
+    pitch: usize,
+}
+
+impl<'a> PixelViewMut<'a> {
+    pub fn new(data: &'a mut [u8], size: (u32, u32), pitch: usize) -> Result<PixelViewMut<'a>, GameError> {
+        check_raw_data(data.len(), size, pitch)?;
+        Ok(PixelViewMut { data, size, pitch })
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.size.0
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.size.1
+    }
+
+    pub fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    pub fn get_pitch(&self) -> usize {
+        self.pitch
+    }
+
+    pub fn get_format(&self) -> PixelFormat {
+        PixelFormat::RGBA8
+    }
+
+    pub fn get(&self, x: i32, y: i32) -> Option<&ColorU8> {
+        let offset = pixel_offset(self.size, self.pitch, x, y)?;
+        Some(&bytes_to_colors(&self.data[offset..offset + 4])[0])
+    }
+
+    pub fn get_mut(&mut self, x: i32, y: i32) -> Option<&mut ColorU8> {
+        let offset = pixel_offset(self.size, self.pitch, x, y)?;
+        Some(&mut bytes_to_colors_mut(&mut self.data[offset..offset + 4])[0])
+    }
+
+    /// Sets the pixel and returns `false` if it is outside of the surface.
+    pub fn set(&mut self, x: i32, y: i32, color: &dyn Color) -> bool {
+        match self.get_mut(x, y) {
+            Some(pixel) => {
+                pixel.set(color);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn row(&self, y: i32) -> Option<&[ColorU8]> {
+        let offset = pixel_offset(self.size, self.pitch, 0, y)?;
+        Some(bytes_to_colors(&self.data[offset..offset + self.size.0 as usize * 4]))
+    }
+
+    pub fn row_mut(&mut self, y: i32) -> Option<&mut [ColorU8]> {
+        let offset = pixel_offset(self.size, self.pitch, 0, y)?;
+        let row_len = self.size.0 as usize * 4;
+        Some(bytes_to_colors_mut(&mut self.data[offset..offset + row_len]))
+    }
+
+    /// Calls `f` with the position and the color of every pixel.
+    pub fn for_each_mut<F: FnMut((i32, i32), &mut ColorU8)>(&mut self, mut f: F) {
+        for y in 0..self.size.1 as i32 {
+            if let Some(row) = self.row_mut(y) {
+                for (x, color) in row.iter_mut().enumerate() {
+                    f((x as i32, y), color);
+                }
+            }
+        }
+    }
+}
+
+impl Index<(i32, i32)> for PixelViewMut<'_> {
+    type Output = ColorU8;
+
+    fn index(&self, position: (i32, i32)) -> &ColorU8 {
+        match self.get(position.0, position.1) {
+            Some(color) => color,
+            None => panic!("{}", out_of_bounds(position, self.size)),
+        }
+    }
+}
+
+impl IndexMut<(i32, i32)> for PixelViewMut<'_> {
+    fn index_mut(&mut self, position: (i32, i32)) -> &mut ColorU8 {
+        let size = self.size;
+        match self.get_mut(position.0, position.1) {
+            Some(color) => color,
+            None => panic!("{}", out_of_bounds(position, size)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod pixel_view_test {
+    use super::*;
+    use crate::rectangle::Rect;
+    use crate::surface::software_surface::SoftwareSurface;
+    use crate::surface::Surface;
+
+    fn surface() -> SoftwareSurface {
+        let data = (0..24).map(|i| i as u8).collect();
+        SoftwareSurface::from_raw((3, 2), data).unwrap()
+    }
+
+    #[test]
+    fn pixel_view_test() {
+        let surface = surface();
+        let pixels = surface.pixels().unwrap();
+        assert_eq!((3, 2), pixels.get_size());
+        assert_eq!(12, pixels.get_pitch());
+        assert_eq!(PixelFormat::RGBA8, pixels.get_format());
+        assert_eq!(ColorU8::new_rgba(4, 5, 6, 7), pixels[(1, 0)]);
+        assert_eq!(Some(&ColorU8::new_rgba(20, 21, 22, 23)), pixels.get(2, 1));
+        assert_eq!(None, pixels.get(3, 0));
+        assert_eq!(None, pixels.get(0, -1));
+        assert_eq!(3, pixels.row(1).unwrap().len());
+        assert_eq!(ColorU8::new_rgba(12, 13, 14, 15), pixels.row(1).unwrap()[0]);
+        assert!(pixels.row(2).is_none());
+        assert_eq!(2, pixels.rows().count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn pixel_view_index_test() {
+        let surface = surface();
+        let _ = surface.pixels().unwrap()[(0, 2)];
+    }
+
+    #[test]
+    fn pixel_view_mut_test() {
+        let mut surface = surface();
+        let mut pixels = surface.pixels_mut().unwrap();
+        pixels[(0, 0)] = ColorU8::new_rgb(255, 0, 0);
+        assert!(pixels.set(2, 1, &ColorU8::new_gray(9)));
+        assert!(!pixels.set(-1, 1, &ColorU8::new_gray(9)));
+        pixels.row_mut(1).unwrap()[0].set_a(0);
+        pixels.for_each_mut(|(x, _), color| color.set_b(x as u8));
+        assert_eq!(&[255, 0, 0, 255], &surface.raw().unwrap()[0..4]);
+        assert_eq!(&[9, 9, 2, 255], &surface.raw().unwrap()[20..24]);
+        assert_eq!(&[12, 13, 0, 0], &surface.raw().unwrap()[12..16]);
+    }
+
+    #[test]
+    fn get_set_at_test() {
+        let mut surface = surface();
+        assert_eq!(ColorU8::new_rgba(16, 17, 18, 19), surface.get_at((1, 1)).unwrap());
+        surface.set_at((1, 1), &ColorU8::new_rgb(1, 2, 3)).unwrap();
+        assert_eq!(ColorU8::new_rgb(1, 2, 3), surface.get_at((1, 1)).unwrap());
+        assert!(matches!(surface.get_at((3, 1)), Err(GameError::InvalidGeometry(_))));
+        assert!(matches!(surface.set_at((0, 2), &ColorU8::new_gray(0)), Err(GameError::InvalidGeometry(_))));
+        assert_eq!(
+            vec![
+                ColorU8::new_rgba(0, 1, 2, 3),
+                ColorU8::new_rgba(4, 5, 6, 7),
+                ColorU8::new_rgba(8, 9, 10, 11)
+            ],
+            surface.get_row(0).unwrap()
+        );
+        assert!(surface.get_row(-1).is_err());
+    }
+
+    #[test]
+    fn subsurface_test() {
+        let mut surface = surface();
+        let mut sub_surface = surface.subsurface(&Rect::new(1, 0, 2, 2)).unwrap();
+        assert_eq!(12, sub_surface.pixels().unwrap().get_pitch());
+        assert_eq!(ColorU8::new_rgba(16, 17, 18, 19), sub_surface.get_at((0, 1)).unwrap());
+        assert_eq!(2, sub_surface.get_row(1).unwrap().len());
+        sub_surface.set_at((1, 1), &ColorU8::new_gray(0)).unwrap();
+        assert!(sub_surface.get_at((2, 0)).is_err());
+        drop(sub_surface);
+        assert_eq!(ColorU8::new_gray(0), surface.get_at((2, 1)).unwrap());
+    }
+}
@@ -0,0 +1,329 @@
+use crate::color::{Color, ColorU8};
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::surface::{BlendMode, Surface};
+use crate::utility::blend_utility::BlendUtility;
+use std::any::Any;
+
+pub struct SoftwareSurface {
+    size: (u32, u32),
+    data: Vec<u8>,
+    color_key: Option<ColorU8>,
+    alpha: u8,
+    clip: Option<Rect>,
+}
+
+impl SoftwareSurface {
+    pub fn new(size: (u32, u32)) -> SoftwareSurface {
+        SoftwareSurface {
+            size,
+            data: vec![0; size.0 as usize * size.1 as usize * 4],
+            color_key: Option::None,
+            alpha: 255,
+            clip: Option::None,
+        }
+    }
+
+    pub fn new_alpha(size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+        Ok(Box::new(SoftwareSurface::new(size)))
+    }
+
+    pub fn from_raw(size: (u32, u32), data: Vec<u8>) -> Result<SoftwareSurface, GameError> {
+        if data.len() != size.0 as usize * size.1 as usize * 4 {
+            Err(GameError::InvalidGeometry("raw data does not match the surface size".to_string()))?
+        }
+        Ok(SoftwareSurface {
+            size,
+            data,
+            color_key: Option::None,
+            alpha: 255,
+            clip: Option::None,
+        })
+    }
+
+    pub fn from_surface(surface: &dyn Surface) -> Result<SoftwareSurface, GameError> {
+        SoftwareSurface::from_surface_rect(surface, &surface.get_rect())
+    }
+
+    /// Copy of the region of the surface, the region is clipped to the surface.
+    pub fn from_surface_rect(surface: &dyn Surface, rect: &Rect) -> Result<SoftwareSurface, GameError> {
+        let rect = surface.get_rect().clip(rect);
+        let (x, y, w) = (rect.x as usize, rect.y as usize, rect.w as usize);
+        let pitch = surface.get_pitch();
+        let raw = surface.raw()?;
+        let mut data = Vec::with_capacity(w * rect.h as usize * 4);
+        for row in y..y + rect.h as usize {
+            data.extend_from_slice(&raw[row * pitch + x * 4..row * pitch + (x + w) * 4]);
+        }
+        SoftwareSurface::from_raw((rect.w as u32, rect.h as u32), data)
+    }
+}
+
+impl Surface for SoftwareSurface {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clone(&self) -> Result<Box<dyn Surface>, GameError> {
+        Ok(Box::new(SoftwareSurface {
+            size: self.size,
+            data: self.data.clone(),
+            color_key: self.color_key,
+            alpha: self.alpha,
+            clip: self.clip.clone(),
+        }))
+    }
+
+    fn modulate_surface_and_color(&self, color: &dyn Color) -> Result<Box<dyn Surface>, GameError> {
+        let mut color_surface = SoftwareSurface::new(self.size);
+        color_surface.fill(color)?;
+        color_surface.blit(self, (0, 0), BlendMode::MultiplyRGBA)?;
+        Ok(Box::new(color_surface))
+    }
+
+    fn get_width(&self) -> u32 {
+        self.size.0
+    }
+
+    fn get_height(&self) -> u32 {
+        self.size.1
+    }
+
+    fn get_size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    fn get_rect(&self) -> Rect {
+        Rect::new(0, 0, self.size.0 as i32, self.size.1 as i32)
+    }
+
+    fn raw(&self) -> Result<&[u8], GameError> {
+        Ok(&self.data)
+    }
+
+    fn raw_mut(&mut self) -> Result<&mut [u8], GameError> {
+        Ok(&mut self.data)
+    }
+
+    fn fill(&mut self, color: &dyn Color) -> Result<(), GameError> {
+        let clip = self.get_clip();
+        BlendUtility::fill_raw(&mut self.data, self.size.0 as usize * 4, &clip, color);
+        Ok(())
+    }
+
+    fn set_colorkey(&mut self, color_key: Option<&dyn Color>) -> Result<(), GameError> {
+        self.color_key = color_key.map(|key| ColorU8::new_rgba(key.r(), key.g(), key.b(), key.a()));
+        Ok(())
+    }
+
+    fn get_colorkey(&self) -> Option<ColorU8> {
+        self.color_key
+    }
+
+    fn set_alpha(&mut self, alpha: u8) -> Result<(), GameError> {
+        self.alpha = alpha;
+        Ok(())
+    }
+
+    fn get_alpha(&self) -> u8 {
+        self.alpha
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) -> Result<(), GameError> {
+        self.clip = clip;
+        Ok(())
+    }
+
+    fn get_clip(&self) -> Rect {
+        match &self.clip {
+            Some(clip) => self.get_rect().clip(clip),
+            None => self.get_rect(),
+        }
+    }
+
+    fn blit(&mut self, source_surface: &dyn Surface, position: (i32, i32), blend_mode: BlendMode) -> Result<Rect, GameError> {
+        let src_rect = source_surface.get_rect().move_(position.0, position.1);
+        let dest_rect = self.get_clip().clip(&src_rect);
+        let src_rect = src_rect.clip(&dest_rect).move_(-position.0, -position.1);
+        let dest_pitch = self.size.0 as usize * 4;
+        let source_pitch = source_surface.get_pitch();
+        BlendUtility::blend_raw_keyed(
+            &mut self.data,
+            dest_pitch,
+            &dest_rect,
+            source_surface.raw()?,
+            source_pitch,
+            &src_rect,
+            blend_mode,
+            source_surface.get_colorkey(),
+            source_surface.get_alpha(),
+        )?;
+        Ok(dest_rect)
+    }
+}
+
+#[cfg(test)]
+mod software_surface_test {
+    use super::*;
+    use crate::color::ColorU8;
+    use crate::test::pixel;
+
+    #[test]
+    fn new_test() {
+        let surface = SoftwareSurface::new((3, 2));
+        assert_eq!((3, 2), surface.get_size());
+        assert_eq!(3, surface.get_width());
+        assert_eq!(2, surface.get_height());
+        assert_eq!(24, surface.raw().unwrap().len());
+        assert!(surface.raw().unwrap().iter().all(|&c| c == 0));
+    }
+
+    #[test]
+    fn from_raw_test() {
+        assert!(SoftwareSurface::from_raw((2, 2), vec![0; 16]).is_ok());
+        assert!(matches!(SoftwareSurface::from_raw((2, 2), vec![0; 15]), Err(GameError::InvalidGeometry(_))));
+    }
+
+    #[test]
+    fn from_surface_rect_test() {
+        let mut surface = SoftwareSurface::new((3, 3));
+        surface.raw_mut().unwrap()[16..20].copy_from_slice(&[1, 2, 3, 4]);
+        let region = SoftwareSurface::from_surface_rect(&surface, &Rect::new(1, 1, 5, 1)).unwrap();
+        assert_eq!((2, 1), region.get_size());
+        assert_eq!([1, 2, 3, 4], pixel(&region, 0, 0));
+        let outside = SoftwareSurface::from_surface_rect(&surface, &Rect::new(5, 5, 2, 2)).unwrap();
+        assert_eq!((0, 0), outside.get_size());
+    }
+
+    #[test]
+    fn fill_test() {
+        let mut surface = SoftwareSurface::new((2, 2));
+        surface.fill(&ColorU8::new(1, 2, 3, 4)).unwrap();
+        assert_eq!([1, 2, 3, 4], pixel(&surface, 0, 0));
+        assert_eq!([1, 2, 3, 4], pixel(&surface, 1, 1));
+    }
+
+    #[test]
+    fn clone_test() {
+        let mut surface = SoftwareSurface::new((2, 2));
+        surface.fill(&ColorU8::new(1, 2, 3, 4)).unwrap();
+        let copy = Surface::clone(&surface).unwrap();
+        surface.fill(&ColorU8::new_gray(0)).unwrap();
+        assert_eq!([1, 2, 3, 4], pixel(copy.as_ref(), 1, 1));
+    }
+
+    #[test]
+    fn raw_mut_test() {
+        let mut surface = SoftwareSurface::new((2, 2));
+        surface.raw_mut().unwrap()[4..8].copy_from_slice(&[5, 6, 7, 8]);
+        assert_eq!([5, 6, 7, 8], pixel(&surface, 1, 0));
+    }
+
+    #[test]
+    fn blit_test() {
+        let mut surface = SoftwareSurface::new((4, 4));
+        let mut source = SoftwareSurface::new((2, 2));
+        source.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        let rect = surface.blit(&source, (3, -1), BlendMode::Blend).unwrap();
+        assert_eq!((3, 0, 1, 1), (rect.x, rect.y, rect.w, rect.h));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 3, 0));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 2, 0));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 3, 1));
+    }
+
+    #[test]
+    fn blit_outside_test() {
+        let mut surface = SoftwareSurface::new((4, 4));
+        let source = SoftwareSurface::new((2, 2));
+        let rect = surface.blit(&source, (10, 10), BlendMode::Blend).unwrap();
+        assert_eq!((0, 0), rect.get_size());
+    }
+
+    #[test]
+    fn blit_blend_test() {
+        let mut surface = SoftwareSurface::new((1, 1));
+        surface.fill(&ColorU8::new_rgb(0, 0, 255)).unwrap();
+        let mut source = SoftwareSurface::new((1, 1));
+        source.fill(&ColorU8::new_rgba(255, 0, 0, 128)).unwrap();
+        surface.blit(&source, (0, 0), BlendMode::Blend).unwrap();
+        assert_eq!([128, 0, 127, 255], pixel(&surface, 0, 0));
+    }
+
+    #[test]
+    fn blit_blend_modes_test() {
+        let mut source = SoftwareSurface::new((1, 1));
+        source.fill(&ColorU8::new_rgb(200, 100, 0)).unwrap();
+        for blend_mode in (0..).map_while(|value| BlendMode::try_from(value).ok()) {
+            let mut surface = SoftwareSurface::new((1, 1));
+            surface.fill(&ColorU8::new_rgb(100, 100, 100)).unwrap();
+            let rect = surface.blit(&source, (0, 0), blend_mode).unwrap();
+            assert_eq!((0, 0, 1, 1), (rect.x, rect.y, rect.w, rect.h));
+        }
+        let mut surface = SoftwareSurface::new((1, 1));
+        surface.fill(&ColorU8::new_rgb(100, 100, 100)).unwrap();
+        surface.blit(&source, (0, 0), BlendMode::Difference).unwrap();
+        assert_eq!([100, 0, 100, 255], pixel(&surface, 0, 0));
+    }
+
+    #[test]
+    fn modulate_surface_and_color_test() {
+        let mut surface = SoftwareSurface::new((1, 1));
+        surface.fill(&ColorU8::new_rgba(255, 128, 0, 255)).unwrap();
+        let modulated = surface.modulate_surface_and_color(&ColorU8::new_rgba(128, 255, 255, 128)).unwrap();
+        assert_eq!([128, 128, 0, 128], pixel(modulated.as_ref(), 0, 0));
+    }
+
+    #[test]
+    fn blit_colorkey_test() {
+        let mut surface = SoftwareSurface::new((2, 1));
+        surface.fill(&ColorU8::new_rgb(0, 0, 255)).unwrap();
+        let mut source = SoftwareSurface::from_raw((2, 1), vec![255, 0, 255, 255, 255, 0, 0, 255]).unwrap();
+        source.set_colorkey(Some(&ColorU8::new_rgb(255, 0, 255))).unwrap();
+        assert_eq!(Some(ColorU8::new_rgb(255, 0, 255)), source.get_colorkey());
+        surface.blit(&source, (0, 0), BlendMode::Blend).unwrap();
+        assert_eq!([0, 0, 255, 255], pixel(&surface, 0, 0));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 1, 0));
+        source.set_colorkey(Option::None).unwrap();
+        assert_eq!(None, source.get_colorkey());
+        surface.blit(&source, (0, 0), BlendMode::Blend).unwrap();
+        assert_eq!([255, 0, 255, 255], pixel(&surface, 0, 0));
+    }
+
+    #[test]
+    fn blit_alpha_test() {
+        let mut surface = SoftwareSurface::new((1, 1));
+        surface.fill(&ColorU8::new_rgb(0, 0, 255)).unwrap();
+        let mut source = SoftwareSurface::new((1, 1));
+        source.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        source.set_alpha(128).unwrap();
+        assert_eq!(128, source.get_alpha());
+        surface.blit(&source, (0, 0), BlendMode::Blend).unwrap();
+        assert_eq!([128, 0, 127, 255], pixel(&surface, 0, 0));
+        let copy = Surface::clone(&source).unwrap();
+        assert_eq!(128, copy.get_alpha());
+    }
+
+    #[test]
+    fn clip_test() {
+        let mut surface = SoftwareSurface::new((4, 4));
+        surface.set_clip(Some(Rect::new(2, 1, 5, 2))).unwrap();
+        let clip = surface.get_clip();
+        assert_eq!((2, 1, 2, 2), (clip.x, clip.y, clip.w, clip.h));
+        surface.fill(&ColorU8::new_rgb(255, 0, 0)).unwrap();
+        assert_eq!(4, surface.raw().unwrap().chunks_exact(4).filter(|p| p[3] > 0).count());
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 3, 2));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 1, 1));
+
+        let mut source = SoftwareSurface::new((3, 3));
+        source.fill(&ColorU8::new_rgb(0, 255, 0)).unwrap();
+        let rect = surface.blit(&source, (0, 0), BlendMode::None).unwrap();
+        assert_eq!((2, 1, 1, 2), (rect.x, rect.y, rect.w, rect.h));
+        assert_eq!([0, 255, 0, 255], pixel(&surface, 2, 2));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 3, 1));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 1, 1));
+
+        surface.set_clip(None).unwrap();
+        let clip = surface.get_clip();
+        assert_eq!((0, 0, 4, 4), (clip.x, clip.y, clip.w, clip.h));
+    }
+}
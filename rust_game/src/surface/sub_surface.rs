@@ -0,0 +1,253 @@
+use crate::color::{Color, ColorU8};
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::surface::software_surface::SoftwareSurface;
+use crate::surface::{BlendMode, Surface};
+use crate::utility::blend_utility::BlendUtility;
+use std::any::Any;
+
+/// View of a region of a parent surface, see `Surface::subsurface`.
+///
+/// The raw data is the slice of the parent's raw data from the top left pixel of the region to the bottom right pixel,
+/// so the rows have the pitch of the parent.
+pub struct SubSurface<'a> {
+    rect: Rect,
+    pitch: usize,
+    data: &'a mut [u8],
+    color_key: Option<ColorU8>,
+    alpha: u8,
+    clip: Option<Rect>,
+}
+
+impl<'a> SubSurface<'a> {
+    /// View of `rect` in the raw data of a parent surface with the size `parent_rect` and the pitch `parent_pitch`.
+    pub fn new(parent_data: &'a mut [u8], parent_pitch: usize, parent_rect: &Rect, rect: &Rect) -> Result<SubSurface<'a>, GameError> {
+        if rect.w < 0 || rect.h < 0 || !parent_rect.contains(rect) {
+            Err(GameError::InvalidGeometry("subsurface rectangle is outside of the surface".to_string()))?
+        }
+        let (start, len) = match rect.w > 0 && rect.h > 0 {
+            true => (rect.y as usize * parent_pitch + rect.x as usize * 4, (rect.h as usize - 1) * parent_pitch + rect.w as usize * 4),
+            false => (0, 0),
+        };
+        if parent_data.len() < start + len {
+            Err(GameError::RawDataUnavailable)?
+        }
+        Ok(SubSurface {
+            rect: rect.clone(),
+            pitch: parent_pitch,
+            data: &mut parent_data[start..start + len],
+            color_key: Option::None,
+            alpha: 255,
+            clip: Option::None,
+        })
+    }
+
+    /// Position of the view in the parent surface.
+    pub fn get_offset(&self) -> (i32, i32) {
+        self.rect.get_top_left()
+    }
+}
+
+/// Value returned by `SubSurface::as_any`, the view borrows its parent and cannot be downcast itself.
+pub struct SubSurfaceView;
+
+impl Surface for SubSurface<'_> {
+    fn as_any(&self) -> &dyn Any {
+        &SubSurfaceView
+    }
+
+    fn clone(&self) -> Result<Box<dyn Surface>, GameError> {
+        let mut surface = SoftwareSurface::from_surface(self)?;
+        surface.set_colorkey(self.color_key.as_ref().map(|key| key as &dyn Color))?;
+        surface.set_alpha(self.alpha)?;
+        Ok(Box::new(surface))
+    }
+
+    fn modulate_surface_and_color(&self, color: &dyn Color) -> Result<Box<dyn Surface>, GameError> {
+        SoftwareSurface::from_surface(self)?.modulate_surface_and_color(color)
+    }
+
+    fn get_width(&self) -> u32 {
+        self.rect.w as u32
+    }
+
+    fn get_height(&self) -> u32 {
+        self.rect.h as u32
+    }
+
+    fn get_size(&self) -> (u32, u32) {
+        (self.rect.w as u32, self.rect.h as u32)
+    }
+
+    fn get_rect(&self) -> Rect {
+        Rect::new(0, 0, self.rect.w, self.rect.h)
+    }
+
+    fn raw(&self) -> Result<&[u8], GameError> {
+        Ok(self.data)
+    }
+
+    fn raw_mut(&mut self) -> Result<&mut [u8], GameError> {
+        Ok(self.data)
+    }
+
+    fn get_pitch(&self) -> usize {
+        self.pitch
+    }
+
+    fn fill(&mut self, color: &dyn Color) -> Result<(), GameError> {
+        let clip = self.get_clip();
+        BlendUtility::fill_raw(self.data, self.pitch, &clip, color);
+        Ok(())
+    }
+
+    fn set_colorkey(&mut self, color_key: Option<&dyn Color>) -> Result<(), GameError> {
+        self.color_key = color_key.map(|key| ColorU8::new_rgba(key.r(), key.g(), key.b(), key.a()));
+        Ok(())
+    }
+
+    fn get_colorkey(&self) -> Option<ColorU8> {
+        self.color_key
+    }
+
+    fn set_alpha(&mut self, alpha: u8) -> Result<(), GameError> {
+        self.alpha = alpha;
+        Ok(())
+    }
+
+    fn get_alpha(&self) -> u8 {
+        self.alpha
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) -> Result<(), GameError> {
+        self.clip = clip;
+        Ok(())
+    }
+
+    fn get_clip(&self) -> Rect {
+        match &self.clip {
+            Some(clip) => self.get_rect().clip(clip),
+            None => self.get_rect(),
+        }
+    }
+
+    fn blit(&mut self, source_surface: &dyn Surface, position: (i32, i32), blend_mode: BlendMode) -> Result<Rect, GameError> {
+        let src_rect = source_surface.get_rect().move_(position.0, position.1);
+        let dest_rect = self.get_clip().clip(&src_rect);
+        let src_rect = src_rect.clip(&dest_rect).move_(-position.0, -position.1);
+        let source_pitch = source_surface.get_pitch();
+        BlendUtility::blend_raw_keyed(
+            self.data,
+            self.pitch,
+            &dest_rect,
+            source_surface.raw()?,
+            source_pitch,
+            &src_rect,
+            blend_mode,
+            source_surface.get_colorkey(),
+            source_surface.get_alpha(),
+        )?;
+        Ok(dest_rect)
+    }
+}
+
+#[cfg(test)]
+mod sub_surface_test {
+    use super::*;
+    use crate::color::ColorU8;
+    use crate::draw::software_draw::SoftwareDraw;
+    use crate::test::pixel;
+    use crate::transform;
+
+    fn red() -> ColorU8 {
+        ColorU8::new_rgb(255, 0, 0)
+    }
+
+    #[test]
+    fn new_test() {
+        let mut surface = SoftwareSurface::new((4, 3));
+        let sub_surface = surface.subsurface(&Rect::new(1, 1, 2, 2)).unwrap();
+        assert_eq!((2, 2), sub_surface.get_size());
+        assert_eq!(16, sub_surface.get_pitch());
+        assert_eq!(24, sub_surface.raw().unwrap().len());
+        assert!(sub_surface.as_any().downcast_ref::<SubSurfaceView>().is_some());
+        assert!(sub_surface.as_any().downcast_ref::<Rect>().is_none());
+        drop(sub_surface);
+        assert!(surface.subsurface(&Rect::new(3, 1, 2, 2)).is_err());
+        assert!(surface.subsurface(&Rect::new(-1, 0, 2, 2)).is_err());
+        assert_eq!((0, 0), surface.subsurface(&Rect::new(4, 3, 0, 0)).unwrap().get_size());
+    }
+
+    #[test]
+    fn fill_test() {
+        let mut surface = SoftwareSurface::new((4, 3));
+        surface.subsurface(&Rect::new(1, 1, 2, 2)).unwrap().fill(&red()).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 1, 1));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 2, 2));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 3, 1));
+        assert_eq!([0, 0, 0, 0], pixel(&surface, 0, 2));
+        assert_eq!(4, surface.raw().unwrap().chunks_exact(4).filter(|p| p[3] > 0).count());
+    }
+
+    #[test]
+    fn blit_test() {
+        let mut surface = SoftwareSurface::new((4, 4));
+        let mut source = SoftwareSurface::new((2, 2));
+        source.fill(&red()).unwrap();
+        let mut sub_surface = surface.subsurface(&Rect::new(1, 1, 3, 2)).unwrap();
+        let rect = sub_surface.blit(&source, (2, 1), BlendMode::None).unwrap();
+        assert_eq!((2, 1, 1, 1), (rect.x, rect.y, rect.w, rect.h));
+        drop(sub_surface);
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 3, 2));
+        assert_eq!(1, surface.raw().unwrap().chunks_exact(4).filter(|p| p[3] > 0).count());
+    }
+
+    #[test]
+    fn blit_from_subsurface_test() {
+        let mut sheet = SoftwareSurface::new((4, 2));
+        sheet.raw_mut().unwrap()[8..12].copy_from_slice(&[1, 2, 3, 255]);
+        sheet.raw_mut().unwrap()[28..32].copy_from_slice(&[4, 5, 6, 255]);
+        let frame = sheet.subsurface(&Rect::new(2, 0, 2, 2)).unwrap();
+        let mut surface = SoftwareSurface::new((2, 2));
+        surface.blit(frame.as_ref(), (0, 0), BlendMode::None).unwrap();
+        assert_eq!([1, 2, 3, 255], pixel(&surface, 0, 0));
+        assert_eq!([4, 5, 6, 255], pixel(&surface, 1, 1));
+        let copy = frame.clone().unwrap();
+        assert_eq!(surface.raw().unwrap(), copy.raw().unwrap());
+    }
+
+    #[test]
+    fn draw_test() {
+        let mut surface = SoftwareSurface::new((6, 6));
+        let draw = SoftwareDraw::new().unwrap();
+        let mut sub_surface = surface.subsurface(&Rect::new(2, 2, 3, 3)).unwrap();
+        let rect = draw.rectangle(sub_surface.as_mut(), false, &red(), Rect::new(-1, 1, 10, 1), -1).unwrap();
+        assert_eq!((0, 1, 3, 1), (rect.x, rect.y, rect.w, rect.h));
+        drop(sub_surface);
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 2, 3));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 4, 3));
+        assert_eq!(3, surface.raw().unwrap().chunks_exact(4).filter(|p| p[3] > 0).count());
+    }
+
+    #[test]
+    fn nested_test() {
+        let mut surface = SoftwareSurface::new((6, 6));
+        {
+            let mut sub_surface = surface.subsurface(&Rect::new(1, 1, 4, 4)).unwrap();
+            let mut nested = sub_surface.subsurface(&Rect::new(1, 2, 2, 1)).unwrap();
+            nested.fill(&red()).unwrap();
+        }
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 2, 3));
+        assert_eq!([255, 0, 0, 255], pixel(&surface, 3, 3));
+        assert_eq!(2, surface.raw().unwrap().chunks_exact(4).filter(|p| p[3] > 0).count());
+    }
+
+    #[test]
+    fn transform_test() {
+        let mut surface = SoftwareSurface::new((4, 4));
+        surface.raw_mut().unwrap()[20..24].copy_from_slice(&[1, 2, 3, 255]);
+        let sub_surface = surface.subsurface(&Rect::new(1, 1, 2, 1)).unwrap();
+        let flipped = transform::flip(sub_surface.as_ref(), true, false).unwrap();
+        assert_eq!(&[0, 0, 0, 0, 1, 2, 3, 255], flipped.raw().unwrap());
+    }
+}
@@ -2,3 +2,14 @@
 mod surface_mock;
 #[cfg(test)]
 pub use surface_mock::SurfaceMock;
+
+#[cfg(test)]
+use crate::surface::Surface;
+
+/// RGBA bytes of the pixel, respecting the pitch of the surface.
+#[cfg(test)]
+pub fn pixel(surface: &dyn Surface, x: usize, y: usize) -> [u8; 4] {
+    let i = y * surface.get_pitch() + x * 4;
+    let raw = surface.raw().unwrap();
+    [raw[i], raw[i + 1], raw[i + 2], raw[i + 3]]
+}
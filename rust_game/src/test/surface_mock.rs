@@ -1,8 +1,8 @@
 use crate::color::{Color, ColorU8};
+use crate::error::GameError;
 use crate::rectangle::Rect;
 use crate::surface::{BlendMode, Surface};
 use std::any::Any;
-use std::error::Error;
 
 pub struct SurfaceMock {
     pub fill_color: ColorU8,
@@ -21,13 +21,13 @@ impl Surface for SurfaceMock {
         self
     }
 
-    fn clone(&self) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    fn clone(&self) -> Result<Box<dyn Surface>, GameError> {
         Ok(Box::new(SurfaceMock {
             fill_color: self.fill_color,
         }))
     }
 
-    fn modulate_surface_and_color(&self, color: &dyn Color) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    fn modulate_surface_and_color(&self, color: &dyn Color) -> Result<Box<dyn Surface>, GameError> {
         Ok(Box::new(SurfaceMock {
             fill_color: ColorU8::new_rgba(color.r(), color.g(), color.b(), color.a()),
         }))
@@ -49,20 +49,20 @@ impl Surface for SurfaceMock {
         Rect::new(0, 0, 0, 0)
     }
 
-    fn raw(&self) -> Result<&[u8], Box<dyn Error>> {
-        Err("test")?
+    fn raw(&self) -> Result<&[u8], GameError> {
+        Err(GameError::Backend("test".to_string()))?
     }
 
-    fn raw_mut(&mut self) -> Result<&mut [u8], Box<dyn Error>> {
-        Err("test")?
+    fn raw_mut(&mut self) -> Result<&mut [u8], GameError> {
+        Err(GameError::Backend("test".to_string()))?
     }
 
-    fn fill(&mut self, color: &dyn Color) -> Result<(), Box<dyn Error>> {
+    fn fill(&mut self, color: &dyn Color) -> Result<(), GameError> {
         self.fill_color.set(color);
         Ok(())
     }
 
-    fn blit(&mut self, _: &dyn Surface, _: (i32, i32), _: BlendMode) -> Result<Rect, Box<dyn Error>> {
+    fn blit(&mut self, _: &dyn Surface, _: (i32, i32), _: BlendMode) -> Result<Rect, GameError> {
         Ok(Rect::new(0, 0, 0, 0))
     }
 }
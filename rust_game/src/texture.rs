@@ -0,0 +1,250 @@
+//! Procedural textures for placeholder art and backgrounds.
+//!
+//! The generators create a surface of the context and compute the color of every pixel at the pixel center.
+//! Gradients and noise map a value in the range [0, 1] to a color with color stops,
+//! which are pairs of a position in the range [0, 1] and a color, sorted by the position.
+
+pub mod noise;
+
+use crate::color::{Color, ColorU8};
+use crate::context::Context;
+use crate::error::GameError;
+use crate::surface::Surface;
+use crate::texture::noise::{fractal_noise, Noise};
+use crate::transform::sin_cos_degrees;
+
+fn generate<F>(context: &dyn Context, size: (u32, u32), pixel_color: F) -> Result<Box<dyn Surface>, GameError>
+where
+    F: Fn(f32, f32) -> ColorU8,
+{
+    let mut surface = context.new_surface_alpha_from_size(size)?;
+    surface.pixels_mut()?.for_each_mut(|(x, y), color| *color = pixel_color(x as f32 + 0.5, y as f32 + 0.5));
+    Ok(surface)
+}
+
+fn to_color_u8(color: &dyn Color) -> ColorU8 {
+    ColorU8::new_rgba(color.r(), color.g(), color.b(), color.a())
+}
+
+fn check_stops(stops: &[(f32, ColorU8)]) -> Result<(), GameError> {
+    if stops.is_empty() {
+        Err(GameError::InvalidArgument("gradient without color stops".to_string()))?
+    }
+    Ok(())
+}
+
+// color at the position `t` of the color stops, which must not be empty, the colors are interpolated linearly between the stops
+fn color_at(stops: &[(f32, ColorU8)], t: f32) -> ColorU8 {
+    let index = stops.iter().position(|stop| stop.0 > t).unwrap_or(stops.len());
+    if index == 0 {
+        return stops[0].1;
+    }
+    if index == stops.len() {
+        return stops[index - 1].1;
+    }
+    let ((t0, color0), (t1, color1)) = (stops[index - 1], stops[index]);
+    let weight = (t - t0) / (t1 - t0);
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * weight).round() as u8;
+    ColorU8::new_rgba(mix(color0.r(), color1.r()), mix(color0.g(), color1.g()), mix(color0.b(), color1.b()), mix(color0.a(), color1.a()))
+}
+
+/// Checkerboard of square cells, the top left cell has the first color.
+pub fn checker(
+    context: &dyn Context,
+    size: (u32, u32),
+    color1: &dyn Color,
+    color2: &dyn Color,
+    cell_size: u32,
+) -> Result<Box<dyn Surface>, GameError> {
+    let colors = [to_color_u8(color1), to_color_u8(color2)];
+    let cell_size = cell_size.max(1) as f32;
+    generate(context, size, |x, y| colors[((x / cell_size).floor() + (y / cell_size).floor()).rem_euclid(2.0) as usize])
+}
+
+/// Stripes of alternating colors with the width `stripe_width`.
+///
+/// The colors change along the direction `angle` in degrees, 0 gives vertical stripes and 90 horizontal stripes.
+pub fn stripes(
+    context: &dyn Context,
+    size: (u32, u32),
+    color1: &dyn Color,
+    color2: &dyn Color,
+    stripe_width: u32,
+    angle: f32,
+) -> Result<Box<dyn Surface>, GameError> {
+    let colors = [to_color_u8(color1), to_color_u8(color2)];
+    let stripe_width = stripe_width.max(1) as f32;
+    let (sin, cos) = sin_cos_degrees(angle);
+    generate(context, size, |x, y| colors[((x * cos + y * sin) / stripe_width).floor().rem_euclid(2.0) as usize])
+}
+
+/// Gradient along the line from `start` to `end`, the colors before `start` and after `end` are the colors of the first and last stop.
+pub fn linear_gradient(
+    context: &dyn Context,
+    size: (u32, u32),
+    start: (f32, f32),
+    end: (f32, f32),
+    stops: &[(f32, ColorU8)],
+) -> Result<Box<dyn Surface>, GameError> {
+    check_stops(stops)?;
+    let direction = (end.0 - start.0, end.1 - start.1);
+    let length_squared = direction.0 * direction.0 + direction.1 * direction.1;
+    generate(context, size, |x, y| {
+        let t = match length_squared > 0.0 {
+            true => ((x - start.0) * direction.0 + (y - start.1) * direction.1) / length_squared,
+            false => 0.0,
+        };
+        color_at(stops, t)
+    })
+}
+
+/// Gradient from the center to the circle with the radius.
+pub fn radial_gradient(
+    context: &dyn Context,
+    size: (u32, u32),
+    center: (f32, f32),
+    radius: f32,
+    stops: &[(f32, ColorU8)],
+) -> Result<Box<dyn Surface>, GameError> {
+    check_stops(stops)?;
+    generate(context, size, |x, y| {
+        let distance = ((x - center.0).powi(2) + (y - center.1).powi(2)).sqrt();
+        color_at(stops, if radius > 0.0 { distance / radius } else { 1.0 })
+    })
+}
+
+/// Gradient around the center, clockwise from the direction `angle` in degrees.
+pub fn conic_gradient(
+    context: &dyn Context,
+    size: (u32, u32),
+    center: (f32, f32),
+    angle: f32,
+    stops: &[(f32, ColorU8)],
+) -> Result<Box<dyn Surface>, GameError> {
+    check_stops(stops)?;
+    generate(context, size, |x, y| {
+        let direction = (y - center.1).atan2(x - center.0).to_degrees();
+        color_at(stops, (direction - angle).rem_euclid(360.0) / 360.0)
+    })
+}
+
+/// Fractal noise with `octaves` layers, the coarsest layer has cells of `cell_size` pixels.
+pub fn noise(
+    context: &dyn Context,
+    size: (u32, u32),
+    noise: Noise,
+    cell_size: f32,
+    octaves: u32,
+    seed: u32,
+    stops: &[(f32, ColorU8)],
+) -> Result<Box<dyn Surface>, GameError> {
+    check_stops(stops)?;
+    let cell_size = cell_size.max(f32::EPSILON);
+    generate(context, size, |x, y| color_at(stops, fractal_noise(noise, x / cell_size, y / cell_size, octaves, seed)))
+}
+
+#[cfg(test)]
+mod texture_test {
+    use super::*;
+    use crate::headless::canvas::FrameCapture;
+    use crate::headless::context::HeadlessContext;
+
+    fn context() -> HeadlessContext {
+        HeadlessContext::new((1, 1), FrameCapture::Discard, Vec::new())
+    }
+
+    fn black() -> ColorU8 {
+        ColorU8::new_gray(0)
+    }
+
+    fn white() -> ColorU8 {
+        ColorU8::new_gray(255)
+    }
+
+    fn gray_stops() -> Vec<(f32, ColorU8)> {
+        vec![(0.0, black()), (1.0, white())]
+    }
+
+    #[test]
+    fn color_at_test() {
+        let stops = vec![
+            (0.25, ColorU8::new_rgb(255, 0, 0)),
+            (0.5, ColorU8::new_rgb(0, 0, 255)),
+            (1.0, ColorU8::new_rgba(0, 0, 255, 0)),
+        ];
+        assert_eq!(ColorU8::new_rgb(255, 0, 0), color_at(&stops, 0.0));
+        assert_eq!(ColorU8::new_rgb(128, 0, 128), color_at(&stops, 0.375));
+        assert_eq!(ColorU8::new_rgb(0, 0, 255), color_at(&stops, 0.5));
+        assert_eq!(ColorU8::new_rgba(0, 0, 255, 128), color_at(&stops, 0.75));
+        assert_eq!(ColorU8::new_rgba(0, 0, 255, 0), color_at(&stops, 2.0));
+        assert_eq!(white(), color_at(&[(0.5, white())], 0.0));
+    }
+
+    #[test]
+    fn checker_test() {
+        let surface = checker(&context(), (5, 3), &black(), &white(), 2).unwrap();
+        assert_eq!((5, 3), surface.get_size());
+        assert_eq!(black(), surface.get_at((1, 1)).unwrap());
+        assert_eq!(white(), surface.get_at((2, 1)).unwrap());
+        assert_eq!(white(), surface.get_at((0, 2)).unwrap());
+        assert_eq!(white(), surface.get_at((4, 2)).unwrap());
+    }
+
+    #[test]
+    fn stripes_test() {
+        let vertical = stripes(&context(), (4, 2), &black(), &white(), 1, 0.0).unwrap();
+        assert_eq!(black(), vertical.get_at((0, 1)).unwrap());
+        assert_eq!(white(), vertical.get_at((1, 1)).unwrap());
+        assert_eq!(black(), vertical.get_at((2, 0)).unwrap());
+        let horizontal = stripes(&context(), (4, 4), &black(), &white(), 2, 90.0).unwrap();
+        assert_eq!(black(), horizontal.get_at((3, 1)).unwrap());
+        assert_eq!(white(), horizontal.get_at((0, 2)).unwrap());
+        let diagonal = stripes(&context(), (4, 4), &black(), &white(), 2, 45.0).unwrap();
+        assert_eq!(diagonal.get_at((0, 3)).unwrap(), diagonal.get_at((3, 0)).unwrap());
+    }
+
+    #[test]
+    fn linear_gradient_test() {
+        let surface = linear_gradient(&context(), (6, 1), (1.0, 0.0), (5.0, 0.0), &gray_stops()).unwrap();
+        assert_eq!(black(), surface.get_at((0, 0)).unwrap());
+        assert_eq!(ColorU8::new_gray(32), surface.get_at((1, 0)).unwrap());
+        assert_eq!(ColorU8::new_gray(159), surface.get_at((3, 0)).unwrap());
+        assert_eq!(white(), surface.get_at((5, 0)).unwrap());
+        assert!(matches!(linear_gradient(&context(), (6, 1), (0.0, 0.0), (1.0, 0.0), &[]), Err(GameError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn radial_gradient_test() {
+        let surface = radial_gradient(&context(), (5, 5), (2.5, 2.5), 2.0, &gray_stops()).unwrap();
+        assert_eq!(black(), surface.get_at((2, 2)).unwrap());
+        assert_eq!(ColorU8::new_gray(128), surface.get_at((3, 2)).unwrap());
+        assert_eq!(surface.get_at((1, 2)).unwrap(), surface.get_at((2, 3)).unwrap());
+        assert_eq!(white(), surface.get_at((0, 0)).unwrap());
+    }
+
+    #[test]
+    fn conic_gradient_test() {
+        let surface = conic_gradient(&context(), (4, 4), (2.0, 2.0), 0.0, &gray_stops()).unwrap();
+        assert_eq!(ColorU8::new_gray(32), surface.get_at((2, 2)).unwrap());
+        assert_eq!(ColorU8::new_gray(96), surface.get_at((1, 2)).unwrap());
+        assert_eq!(ColorU8::new_gray(159), surface.get_at((1, 1)).unwrap());
+        assert_eq!(ColorU8::new_gray(223), surface.get_at((2, 1)).unwrap());
+        let rotated = conic_gradient(&context(), (4, 4), (2.0, 2.0), 90.0, &gray_stops()).unwrap();
+        assert_eq!(ColorU8::new_gray(32), rotated.get_at((1, 2)).unwrap());
+    }
+
+    #[test]
+    fn noise_test() {
+        for kind in [Noise::Value, Noise::Perlin, Noise::Simplex] {
+            let surface = noise(&context(), (16, 16), kind, 4.0, 3, 42, &gray_stops()).unwrap();
+            let same = noise(&context(), (16, 16), kind, 4.0, 3, 42, &gray_stops()).unwrap();
+            let other = noise(&context(), (16, 16), kind, 4.0, 3, 43, &gray_stops()).unwrap();
+            assert_eq!(surface.raw().unwrap(), same.raw().unwrap());
+            assert_ne!(surface.raw().unwrap(), other.raw().unwrap());
+            let pixels = surface.pixels().unwrap();
+            let grays: Vec<u8> = pixels.rows().flatten().map(|color| color.r()).collect();
+            assert!(pixels.rows().flatten().all(|color| color.r() == color.g() && color.a() == 255));
+            assert!(grays.iter().max().unwrap() - grays.iter().min().unwrap() > 64, "{:?}", kind);
+        }
+    }
+}
@@ -0,0 +1,158 @@
+//! Gradient and value noise functions of 2D coordinates.
+//!
+//! The functions are deterministic for a seed and return values in the range [0, 1].
+//! The lattice of the noise has a spacing of 1, so the coordinates are usually divided by a cell size.
+
+/// Kind of noise used by `texture::noise`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Noise {
+    Value,
+    Perlin,
+    Simplex,
+}
+
+const GRADIENTS: [(f32, f32); 8] = [
+    (1.0, 1.0),
+    (-1.0, 1.0),
+    (1.0, -1.0),
+    (-1.0, -1.0),
+    (1.0, 0.0),
+    (-1.0, 0.0),
+    (0.0, 1.0),
+    (0.0, -1.0),
+];
+
+// integer hash of a lattice point
+fn hash(x: i32, y: i32, seed: u32) -> u32 {
+    let mut h = seed.wrapping_mul(0x9e37_79b9) ^ (x as u32).wrapping_mul(0x85eb_ca6b) ^ (y as u32).wrapping_mul(0xc2b2_ae35);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb_352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846c_a68b);
+    h ^ (h >> 16)
+}
+
+fn gradient_dot(x: i32, y: i32, seed: u32, dx: f32, dy: f32) -> f32 {
+    let gradient = GRADIENTS[(hash(x, y, seed) & 7) as usize];
+    gradient.0 * dx + gradient.1 * dy
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Interpolated random values at the lattice points.
+pub fn value_noise(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let (tx, ty) = (fade(x - x0), fade(y - y0));
+    let value = |dx: i32, dy: i32| hash(ix + dx, iy + dy, seed) as f32 / u32::MAX as f32;
+    lerp(lerp(value(0, 0), value(1, 0), tx), lerp(value(0, 1), value(1, 1), tx), ty)
+}
+
+/// Improved Perlin noise with random gradients at the lattice points.
+pub fn perlin_noise(x: f32, y: f32, seed: u32) -> f32 {
+    let (x0, y0) = (x.floor(), y.floor());
+    let (ix, iy) = (x0 as i32, y0 as i32);
+    let (fx, fy) = (x - x0, y - y0);
+    let (tx, ty) = (fade(fx), fade(fy));
+    let n00 = gradient_dot(ix, iy, seed, fx, fy);
+    let n10 = gradient_dot(ix + 1, iy, seed, fx - 1.0, fy);
+    let n01 = gradient_dot(ix, iy + 1, seed, fx, fy - 1.0);
+    let n11 = gradient_dot(ix + 1, iy + 1, seed, fx - 1.0, fy - 1.0);
+    let noise = lerp(lerp(n00, n10, tx), lerp(n01, n11, tx), ty);
+    (noise * 0.5 + 0.5).clamp(0.0, 1.0)
+}
+
+/// Simplex noise on a lattice of triangles.
+pub fn simplex_noise(x: f32, y: f32, seed: u32) -> f32 {
+    const F2: f32 = 0.366_025_4; // (sqrt(3) - 1) / 2
+    const G2: f32 = 0.211_324_87; // (3 - sqrt(3)) / 6
+    let s = (x + y) * F2;
+    let (i, j) = ((x + s).floor(), (y + s).floor());
+    let t = (i + j) * G2;
+    let (x0, y0) = (x - (i - t), y - (j - t));
+    let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+    let corners = [
+        (0, 0, x0, y0),
+        (i1, j1, x0 - i1 as f32 + G2, y0 - j1 as f32 + G2),
+        (1, 1, x0 - 1.0 + 2.0 * G2, y0 - 1.0 + 2.0 * G2),
+    ];
+    let noise: f32 = corners
+        .iter()
+        .map(|&(di, dj, dx, dy)| {
+            let t = 0.5 - dx * dx - dy * dy;
+            match t > 0.0 {
+                true => t.powi(4) * gradient_dot(i as i32 + di, j as i32 + dj, seed, dx, dy),
+                false => 0.0,
+            }
+        })
+        .sum();
+    (noise * 35.0 + 0.5).clamp(0.0, 1.0)
+}
+
+/// Sum of `octaves` layers of the noise, each with double the frequency and half the amplitude of the previous one.
+pub fn fractal_noise(noise: Noise, x: f32, y: f32, octaves: u32, seed: u32) -> f32 {
+    let noise_function = match noise {
+        Noise::Value => value_noise,
+        Noise::Perlin => perlin_noise,
+        Noise::Simplex => simplex_noise,
+    };
+    let (mut sum, mut amplitude_sum, mut amplitude, mut frequency) = (0.0, 0.0, 1.0, 1.0);
+    for octave in 0..octaves.max(1) {
+        sum += noise_function(x * frequency, y * frequency, seed.wrapping_add(octave)) * amplitude;
+        amplitude_sum += amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    sum / amplitude_sum
+}
+
+#[cfg(test)]
+mod noise_test {
+    use super::*;
+
+    fn samples() -> impl Iterator<Item = (f32, f32)> {
+        (0..400).map(|i| ((i % 20) as f32 * 0.37 - 3.0, (i / 20) as f32 * 0.29 - 2.0))
+    }
+
+    #[test]
+    fn range_test() {
+        for noise in [Noise::Value, Noise::Perlin, Noise::Simplex] {
+            let values: Vec<f32> = samples().map(|(x, y)| fractal_noise(noise, x, y, 3, 7)).collect();
+            assert!(values.iter().all(|value| (0.0..=1.0).contains(value)), "{:?}", noise);
+            let min = values.iter().cloned().fold(f32::MAX, f32::min);
+            let max = values.iter().cloned().fold(f32::MIN, f32::max);
+            assert!(max - min > 0.3, "{:?} {} {}", noise, min, max);
+        }
+    }
+
+    #[test]
+    fn seed_test() {
+        for noise in [Noise::Value, Noise::Perlin, Noise::Simplex] {
+            assert_eq!(fractal_noise(noise, 1.3, 2.7, 2, 1), fractal_noise(noise, 1.3, 2.7, 2, 1));
+            assert!(samples().any(|(x, y)| fractal_noise(noise, x, y, 1, 1) != fractal_noise(noise, x, y, 1, 2)));
+        }
+    }
+
+    #[test]
+    fn lattice_test() {
+        assert_eq!(0.5, perlin_noise(3.0, -2.0, 5));
+        assert_eq!(0.5, simplex_noise(0.0, 0.0, 5));
+        assert_eq!(hash(3, -2, 5) as f32 / u32::MAX as f32, value_noise(3.0, -2.0, 5));
+    }
+
+    #[test]
+    fn continuity_test() {
+        for noise in [Noise::Value, Noise::Perlin, Noise::Simplex] {
+            for (x, y) in samples() {
+                let difference = (fractal_noise(noise, x, y, 1, 3) - fractal_noise(noise, x + 0.001, y, 1, 3)).abs();
+                assert!(difference < 0.02, "{:?} ({}, {})", noise, x, y);
+            }
+        }
+    }
+}
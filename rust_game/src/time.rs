@@ -1,5 +1,5 @@
 use crate::context::ContextData;
-use std::error::Error;
+use crate::error::GameError;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -13,7 +13,7 @@ pub struct TimeStd {
 }
 
 impl TimeStd {
-    pub fn from(context: Arc<ContextData>) -> Result<TimeStd, Box<dyn Error>> {
+    pub fn from(context: Arc<ContextData>) -> Result<TimeStd, GameError> {
         Ok(TimeStd {
             context_data: context.clone(),
         })
@@ -0,0 +1,499 @@
+//! Transformations which create a new surface from a surface.
+//!
+//! The free functions work on the raw RGBA8 data of any `Surface` and return a `SoftwareSurface`.
+//! The `Transform` trait gives access to the same functions, backends can override them with a faster implementation.
+
+use crate::error::GameError;
+use crate::surface::software_surface::SoftwareSurface;
+use crate::surface::Surface;
+
+pub trait Transform {
+    fn flip(&self, surface: &dyn Surface, flip_x: bool, flip_y: bool) -> Result<Box<dyn Surface>, GameError> {
+        flip(surface, flip_x, flip_y)
+    }
+
+    fn rotate(&self, surface: &dyn Surface, angle: f32) -> Result<Box<dyn Surface>, GameError> {
+        rotate(surface, angle)
+    }
+
+    fn scale(&self, surface: &dyn Surface, size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+        scale(surface, size)
+    }
+
+    fn smooth_scale(&self, surface: &dyn Surface, size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+        smooth_scale(surface, size)
+    }
+
+    fn rotozoom(&self, surface: &dyn Surface, angle: f32, scale: f32) -> Result<Box<dyn Surface>, GameError> {
+        rotozoom(surface, angle, scale)
+    }
+
+    fn scale2x(&self, surface: &dyn Surface) -> Result<Box<dyn Surface>, GameError> {
+        scale2x(surface)
+    }
+
+    fn laplacian(&self, surface: &dyn Surface) -> Result<Box<dyn Surface>, GameError> {
+        laplacian(surface)
+    }
+}
+
+/// `Transform` implementation which uses the free functions of this module.
+pub struct SoftwareTransform {}
+
+impl Transform for SoftwareTransform {}
+
+// sine and cosine of the angle in degrees, exact for multiples of 90 degrees
+pub(crate) fn sin_cos_degrees(angle: f32) -> (f32, f32) {
+    let angle = angle.rem_euclid(360.0);
+    if angle == 0.0 {
+        (0.0, 1.0)
+    } else if angle == 90.0 {
+        (1.0, 0.0)
+    } else if angle == 180.0 {
+        (0.0, -1.0)
+    } else if angle == 270.0 {
+        (-1.0, 0.0)
+    } else {
+        angle.to_radians().sin_cos()
+    }
+}
+
+// creates a surface of `size` whose pixels are copied from the source pixels returned by `source_pixel`,
+// pixels without source pixel are transparent
+fn remap<F>(surface: &dyn Surface, size: (u32, u32), source_pixel: F) -> Result<Box<dyn Surface>, GameError>
+where
+    F: Fn(usize, usize) -> Option<(usize, usize)>,
+{
+    let pitch = surface.get_pitch();
+    let source = surface.raw()?;
+    let mut data = vec![0; size.0 as usize * size.1 as usize * 4];
+    for y in 0..size.1 as usize {
+        for x in 0..size.0 as usize {
+            if let Some((sx, sy)) = source_pixel(x, y) {
+                let i = (y * size.0 as usize + x) * 4;
+                let si = sy * pitch + sx * 4;
+                data[i..i + 4].copy_from_slice(&source[si..si + 4]);
+            }
+        }
+    }
+    Ok(Box::new(SoftwareSurface::from_raw(size, data)?))
+}
+
+/// Mirrors the surface horizontally if `flip_x` is set and vertically if `flip_y` is set.
+pub fn flip(surface: &dyn Surface, flip_x: bool, flip_y: bool) -> Result<Box<dyn Surface>, GameError> {
+    let (w, h) = (surface.get_width() as usize, surface.get_height() as usize);
+    remap(surface, surface.get_size(), |x, y| Some((if flip_x { w - 1 - x } else { x }, if flip_y { h - 1 - y } else { y })))
+}
+
+/// Rotates the surface counterclockwise by `angle` degrees without filtering.
+///
+/// The new surface has the size of the bounding box of the rotated surface and is transparent outside of the rotated surface.
+/// Rotations by multiples of 90 degrees are exact.
+pub fn rotate(surface: &dyn Surface, angle: f32) -> Result<Box<dyn Surface>, GameError> {
+    let source_size = surface.get_size();
+    let size = rotozoom_size(source_size, angle, 1.0);
+    let (sin, cos) = sin_cos_degrees(angle);
+    let source_center = (source_size.0 as f32 / 2.0, source_size.1 as f32 / 2.0);
+    let center = (size.0 as f32 / 2.0, size.1 as f32 / 2.0);
+    remap(surface, size, |x, y| {
+        let dx = x as f32 + 0.5 - center.0;
+        let dy = y as f32 + 0.5 - center.1;
+        let sx = (dx * cos - dy * sin + source_center.0).floor();
+        let sy = (dx * sin + dy * cos + source_center.1).floor();
+        match sx >= 0.0 && sy >= 0.0 && sx < source_size.0 as f32 && sy < source_size.1 as f32 {
+            true => Some((sx as usize, sy as usize)),
+            false => Option::None,
+        }
+    })
+}
+
+/// Resizes the surface to `size` with nearest neighbor sampling.
+pub fn scale(surface: &dyn Surface, size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+    let (w, h) = (surface.get_width() as usize, surface.get_height() as usize);
+    let (tw, th) = (size.0 as usize, size.1 as usize);
+    if w == 0 || h == 0 {
+        return Ok(Box::new(SoftwareSurface::new(size)));
+    }
+    remap(surface, size, |x, y| Some(((2 * x + 1) * w / (2 * tw), (2 * y + 1) * h / (2 * th))))
+}
+
+// weights of the source pixels for each target pixel,
+// a box filter is used if the size is reduced and a linear filter if the size is enlarged
+fn resample_weights(source_len: usize, target_len: usize) -> Vec<Vec<(usize, f32)>> {
+    let ratio = source_len as f32 / target_len as f32;
+    (0..target_len)
+        .map(|i| match source_len >= target_len {
+            true => {
+                let (start, end) = (i as f32 * ratio, (i + 1) as f32 * ratio);
+                (start.floor() as usize..(end.ceil() as usize).min(source_len))
+                    .map(|j| (j, ((j + 1) as f32).min(end) - (j as f32).max(start)))
+                    .filter(|(_, overlap)| *overlap > 0.0)
+                    .map(|(j, overlap)| (j, overlap / ratio))
+                    .collect()
+            }
+            false => {
+                let center = ((i as f32 + 0.5) * ratio - 0.5).clamp(0.0, (source_len - 1) as f32);
+                let j = center.floor() as usize;
+                let f = center - j as f32;
+                vec![(j, 1.0 - f), ((j + 1).min(source_len - 1), f)]
+            }
+        })
+        .collect()
+}
+
+/// Resizes the surface to `size` with filtering.
+///
+/// The color of the pixels is averaged with a box filter if the size is reduced and interpolated bilinearly if the size is enlarged.
+/// The colors are weighted by their alpha, so the color of transparent pixels does not bleed into the result.
+pub fn smooth_scale(surface: &dyn Surface, size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+    let (w, h) = (surface.get_width() as usize, surface.get_height() as usize);
+    let (tw, th) = (size.0 as usize, size.1 as usize);
+    if w == 0 || h == 0 || tw == 0 || th == 0 {
+        return Ok(Box::new(SoftwareSurface::new(size)));
+    }
+    let (pitch, source) = (surface.get_pitch(), surface.raw()?);
+    let premultiplied: Vec<f32> = (0..h)
+        .flat_map(|y| source[y * pitch..y * pitch + w * 4].chunks_exact(4))
+        .flat_map(|p| {
+            let a = p[3] as f32;
+            [p[0] as f32 * a, p[1] as f32 * a, p[2] as f32 * a, a]
+        })
+        .collect();
+    let mut rows = vec![0.0f32; tw * h * 4];
+    let x_weights = resample_weights(w, tw);
+    for y in 0..h {
+        for (x, weights) in x_weights.iter().enumerate() {
+            for &(sx, weight) in weights {
+                for c in 0..4 {
+                    rows[(y * tw + x) * 4 + c] += premultiplied[(y * w + sx) * 4 + c] * weight;
+                }
+            }
+        }
+    }
+    let mut data = vec![0; tw * th * 4];
+    for (y, weights) in resample_weights(h, th).iter().enumerate() {
+        for x in 0..tw {
+            let mut color = [0.0f32; 4];
+            for &(sy, weight) in weights {
+                for c in 0..4 {
+                    color[c] += rows[(sy * tw + x) * 4 + c] * weight;
+                }
+            }
+            if color[3] > 0.0 {
+                let i = (y * tw + x) * 4;
+                for c in 0..3 {
+                    data[i + c] = (color[c] / color[3]).round().min(255.0) as u8;
+                }
+                data[i + 3] = color[3].round().min(255.0) as u8;
+            }
+        }
+    }
+    Ok(Box::new(SoftwareSurface::from_raw(size, data)?))
+}
+
+/// Size of the bounding box of a surface which is rotated by `angle` degrees and scaled by `scale`.
+pub fn rotozoom_size(size: (u32, u32), angle: f32, scale: f32) -> (u32, u32) {
+    let (sin, cos) = sin_cos_degrees(angle);
+    let (w, h) = (size.0 as f32 * scale, size.1 as f32 * scale);
+    let rotated_w = w * cos.abs() + h * sin.abs();
+    let rotated_h = w * sin.abs() + h * cos.abs();
+    ((rotated_w - 1e-3).ceil().max(0.0) as u32, (rotated_h - 1e-3).ceil().max(0.0) as u32)
+}
+
+// bilinear interpolation with premultiplied alpha, positions outside of the surface are transparent
+fn sample_bilinear(data: &[u8], size: (u32, u32), pitch: usize, x: f32, y: f32) -> [u8; 4] {
+    const TOLERANCE: f32 = 1e-3;
+    if x < -0.5 - TOLERANCE || y < -0.5 - TOLERANCE || x > size.0 as f32 - 0.5 + TOLERANCE || y > size.1 as f32 - 0.5 + TOLERANCE {
+        return [0, 0, 0, 0];
+    }
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let mut color = [0.0f32; 4];
+    for (dx, dy, weight) in [
+        (0, 0, (1.0 - fx) * (1.0 - fy)),
+        (1, 0, fx * (1.0 - fy)),
+        (0, 1, (1.0 - fx) * fy),
+        (1, 1, fx * fy),
+    ] {
+        if weight <= 0.0 {
+            continue;
+        }
+        // the border pixels are extended to the border of the surface
+        let px = (x0 as i32 + dx).clamp(0, size.0 as i32 - 1);
+        let py = (y0 as i32 + dy).clamp(0, size.1 as i32 - 1);
+        let i = py as usize * pitch + px as usize * 4;
+        let alpha = data[i + 3] as f32 * weight;
+        for c in 0..3 {
+            color[c] += data[i + c] as f32 * alpha;
+        }
+        color[3] += alpha;
+    }
+    if color[3] <= 0.0 {
+        return [0, 0, 0, 0];
+    }
+    [
+        (color[0] / color[3]).round() as u8,
+        (color[1] / color[3]).round() as u8,
+        (color[2] / color[3]).round() as u8,
+        color[3].round() as u8,
+    ]
+}
+
+/// Rotates the surface counterclockwise by `angle` degrees and scales it by `scale`, with bilinear filtering.
+///
+/// The new surface has the size of the bounding box of the transformed surface, see `rotozoom_size`.
+pub fn rotozoom(surface: &dyn Surface, angle: f32, scale: f32) -> Result<Box<dyn Surface>, GameError> {
+    if scale <= 0.0 {
+        Err(GameError::InvalidGeometry(format!("scale {} is not positive", scale)))?
+    }
+    let source_size = surface.get_size();
+    let (pitch, source) = (surface.get_pitch(), surface.raw()?);
+    let size = rotozoom_size(source_size, angle, scale);
+    let mut target = SoftwareSurface::new(size);
+    let data = target.raw_mut()?;
+    let (sin, cos) = sin_cos_degrees(angle);
+    let source_center = (source_size.0 as f32 / 2.0, source_size.1 as f32 / 2.0);
+    let center = (size.0 as f32 / 2.0, size.1 as f32 / 2.0);
+    for y in 0..size.1 as usize {
+        for x in 0..size.0 as usize {
+            let dx = x as f32 + 0.5 - center.0;
+            let dy = y as f32 + 0.5 - center.1;
+            // inverse of the counterclockwise rotation in screen coordinates
+            let sx = (dx * cos - dy * sin) / scale + source_center.0 - 0.5;
+            let sy = (dx * sin + dy * cos) / scale + source_center.1 - 0.5;
+            let i = (y * size.0 as usize + x) * 4;
+            data[i..i + 4].copy_from_slice(&sample_bilinear(source, source_size, pitch, sx, sy));
+        }
+    }
+    Ok(Box::new(target))
+}
+
+/// Doubles the size of the surface with the Scale2x (EPX) algorithm, which keeps edges of pixel art sharp.
+pub fn scale2x(surface: &dyn Surface) -> Result<Box<dyn Surface>, GameError> {
+    let (w, h) = (surface.get_width() as usize, surface.get_height() as usize);
+    let (pitch, source) = (surface.get_pitch(), surface.raw()?);
+    let pixel = |x: usize, y: usize| -> [u8; 4] {
+        let i = y * pitch + x * 4;
+        [source[i], source[i + 1], source[i + 2], source[i + 3]]
+    };
+    let mut data = vec![0; w * h * 16];
+    for y in 0..h {
+        for x in 0..w {
+            let p = pixel(x, y);
+            let a = pixel(x, y.saturating_sub(1));
+            let b = pixel((x + 1).min(w - 1), y);
+            let c = pixel(x.saturating_sub(1), y);
+            let d = pixel(x, (y + 1).min(h - 1));
+            let e = [
+                if c == a && c != d && a != b { a } else { p },
+                if a == b && a != c && b != d { b } else { p },
+                if d == c && d != b && c != a { c } else { p },
+                if b == d && b != a && d != c { d } else { p },
+            ];
+            for (k, color) in e.iter().enumerate() {
+                let i = ((2 * y + k / 2) * 2 * w + 2 * x + k % 2) * 4;
+                data[i..i + 4].copy_from_slice(color);
+            }
+        }
+    }
+    Ok(Box::new(SoftwareSurface::from_raw((w as u32 * 2, h as u32 * 2), data)?))
+}
+
+/// Edge detection with a Laplacian filter.
+///
+/// The color channels are the clamped sum of the differences to the 8 neighbors inside of the surface, the alpha channel is kept.
+pub fn laplacian(surface: &dyn Surface) -> Result<Box<dyn Surface>, GameError> {
+    let (w, h) = (surface.get_width() as i32, surface.get_height() as i32);
+    let (pitch, source) = (surface.get_pitch(), surface.raw()?);
+    let mut data = vec![0; (w * h * 4) as usize];
+    for y in 0..h {
+        for x in 0..w {
+            let i = y as usize * pitch + x as usize * 4;
+            let mut sum = [0i32; 3];
+            for (nx, ny) in (-1..=1).flat_map(|dy| (-1..=1).map(move |dx| (x + dx, y + dy))) {
+                if (nx, ny) == (x, y) || nx < 0 || ny < 0 || nx >= w || ny >= h {
+                    continue;
+                }
+                let ni = ny as usize * pitch + nx as usize * 4;
+                for c in 0..3 {
+                    sum[c] += source[i + c] as i32 - source[ni + c] as i32;
+                }
+            }
+            let ti = ((y * w + x) * 4) as usize;
+            for c in 0..3 {
+                data[ti + c] = sum[c].clamp(0, 255) as u8;
+            }
+            data[ti + 3] = source[i + 3];
+        }
+    }
+    Ok(Box::new(SoftwareSurface::from_raw(surface.get_size(), data)?))
+}
+
+#[cfg(test)]
+mod transform_test {
+    use super::*;
+    use crate::test::pixel;
+
+    // 2x1 surface with a red pixel on the left and a green pixel on the right
+    fn red_green() -> SoftwareSurface {
+        SoftwareSurface::from_raw((2, 1), vec![255, 0, 0, 255, 0, 255, 0, 255]).unwrap()
+    }
+
+    #[test]
+    fn rotozoom_size_test() {
+        assert_eq!((10, 20), rotozoom_size((10, 20), 0.0, 1.0));
+        assert_eq!((20, 10), rotozoom_size((10, 20), 90.0, 1.0));
+        assert_eq!((10, 20), rotozoom_size((10, 20), 180.0, 1.0));
+        assert_eq!((20, 40), rotozoom_size((10, 20), 0.0, 2.0));
+        assert_eq!((15, 15), rotozoom_size((10, 10), 45.0, 1.0));
+    }
+
+    #[test]
+    fn rotozoom_identity_test() {
+        let surface = red_green();
+        let rotated = rotozoom(&surface, 0.0, 1.0).unwrap();
+        assert_eq!(surface.raw().unwrap(), rotated.raw().unwrap());
+    }
+
+    #[test]
+    fn rotozoom_rotate_test() {
+        let rotated = rotozoom(&red_green(), 90.0, 1.0).unwrap();
+        assert_eq!((1, 2), rotated.get_size());
+        assert_eq!([0, 255, 0, 255], pixel(rotated.as_ref(), 0, 0));
+        assert_eq!([255, 0, 0, 255], pixel(rotated.as_ref(), 0, 1));
+        let rotated = rotozoom(&red_green(), -90.0, 1.0).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(rotated.as_ref(), 0, 0));
+        assert_eq!([0, 255, 0, 255], pixel(rotated.as_ref(), 0, 1));
+        let rotated = rotozoom(&red_green(), 180.0, 1.0).unwrap();
+        assert_eq!([0, 255, 0, 255], pixel(rotated.as_ref(), 0, 0));
+        assert_eq!([255, 0, 0, 255], pixel(rotated.as_ref(), 1, 0));
+    }
+
+    #[test]
+    fn rotozoom_scale_test() {
+        let scaled = rotozoom(&red_green(), 0.0, 2.0).unwrap();
+        assert_eq!((4, 2), scaled.get_size());
+        assert_eq!([255, 0, 0, 255], pixel(scaled.as_ref(), 0, 0));
+        assert_eq!([0, 255, 0, 255], pixel(scaled.as_ref(), 3, 1));
+        let blended = pixel(scaled.as_ref(), 1, 0);
+        assert!(blended[0] > 128 && blended[1] > 0 && blended[3] == 255);
+        assert!(matches!(rotozoom(&red_green(), 0.0, 0.0), Err(GameError::InvalidGeometry(_))));
+    }
+
+    #[test]
+    fn rotozoom_transparent_border_test() {
+        let mut surface = SoftwareSurface::new((4, 4));
+        surface.raw_mut().unwrap().iter_mut().for_each(|c| *c = 255);
+        let rotated = rotozoom(&surface, 45.0, 1.0).unwrap();
+        assert_eq!((6, 6), rotated.get_size());
+        assert_eq!(0, pixel(rotated.as_ref(), 0, 0)[3]);
+        assert_eq!([255, 255, 255, 255], pixel(rotated.as_ref(), 3, 3));
+    }
+
+    #[test]
+    fn flip_test() {
+        let flipped = flip(&red_green(), true, false).unwrap();
+        assert_eq!([0, 255, 0, 255], pixel(flipped.as_ref(), 0, 0));
+        assert_eq!([255, 0, 0, 255], pixel(flipped.as_ref(), 1, 0));
+        let surface = SoftwareSurface::from_raw((1, 2), vec![1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        let flipped = flip(&surface, false, true).unwrap();
+        assert_eq!(&[5, 6, 7, 8, 1, 2, 3, 4], flipped.raw().unwrap());
+        let flipped = flip(&surface, false, false).unwrap();
+        assert_eq!(surface.raw().unwrap(), flipped.raw().unwrap());
+    }
+
+    #[test]
+    fn rotate_test() {
+        let rotated = rotate(&red_green(), 90.0).unwrap();
+        assert_eq!((1, 2), rotated.get_size());
+        assert_eq!([0, 255, 0, 255], pixel(rotated.as_ref(), 0, 0));
+        assert_eq!([255, 0, 0, 255], pixel(rotated.as_ref(), 0, 1));
+        let rotated = rotate(&red_green(), -270.0).unwrap();
+        assert_eq!([0, 255, 0, 255], pixel(rotated.as_ref(), 0, 0));
+        let rotated = rotate(&red_green(), 180.0).unwrap();
+        assert_eq!([0, 255, 0, 255], pixel(rotated.as_ref(), 0, 0));
+        assert_eq!([255, 0, 0, 255], pixel(rotated.as_ref(), 1, 0));
+    }
+
+    #[test]
+    fn rotate_nearest_test() {
+        let mut surface = SoftwareSurface::new((4, 4));
+        surface.raw_mut().unwrap().iter_mut().for_each(|c| *c = 200);
+        let rotated = rotate(&surface, 45.0).unwrap();
+        assert_eq!((6, 6), rotated.get_size());
+        assert_eq!([0, 0, 0, 0], pixel(rotated.as_ref(), 0, 0));
+        assert!(rotated.raw().unwrap().iter().all(|&c| c == 0 || c == 200));
+        assert_eq!([200, 200, 200, 200], pixel(rotated.as_ref(), 3, 3));
+    }
+
+    #[test]
+    fn scale_test() {
+        let scaled = scale(&red_green(), (4, 3)).unwrap();
+        assert_eq!((4, 3), scaled.get_size());
+        for y in 0..3 {
+            assert_eq!([255, 0, 0, 255], pixel(scaled.as_ref(), 1, y));
+            assert_eq!([0, 255, 0, 255], pixel(scaled.as_ref(), 2, y));
+        }
+        let scaled = scale(&red_green(), (1, 1)).unwrap();
+        assert_eq!([0, 255, 0, 255], pixel(scaled.as_ref(), 0, 0));
+        assert_eq!((0, 0), scale(&red_green(), (0, 0)).unwrap().get_size());
+    }
+
+    #[test]
+    fn smooth_scale_test() {
+        let scaled = smooth_scale(&red_green(), (2, 1)).unwrap();
+        assert_eq!(red_green().raw().unwrap(), scaled.raw().unwrap());
+        let scaled = smooth_scale(&red_green(), (1, 1)).unwrap();
+        assert_eq!([128, 128, 0, 255], pixel(scaled.as_ref(), 0, 0));
+        let scaled = smooth_scale(&red_green(), (4, 1)).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(scaled.as_ref(), 0, 0));
+        assert_eq!([191, 64, 0, 255], pixel(scaled.as_ref(), 1, 0));
+        assert_eq!([0, 255, 0, 255], pixel(scaled.as_ref(), 3, 0));
+    }
+
+    #[test]
+    fn smooth_scale_alpha_test() {
+        let surface = SoftwareSurface::from_raw((2, 1), vec![255, 0, 0, 0, 0, 255, 0, 255]).unwrap();
+        let scaled = smooth_scale(&surface, (1, 1)).unwrap();
+        assert_eq!([0, 255, 0, 128], pixel(scaled.as_ref(), 0, 0));
+        let scaled = smooth_scale(&surface, (1, 0)).unwrap();
+        assert_eq!((1, 0), scaled.get_size());
+    }
+
+    #[test]
+    fn scale2x_test() {
+        // diagonal of black pixels on a white 2x2 surface
+        let (w, b) = ([255u8, 255, 255, 255], [0u8, 0, 0, 255]);
+        let surface = SoftwareSurface::from_raw((2, 2), [b, w, w, b].concat()).unwrap();
+        let scaled = scale2x(&surface).unwrap();
+        assert_eq!((4, 4), scaled.get_size());
+        // the diagonal is connected
+        assert_eq!(b, pixel(scaled.as_ref(), 0, 0));
+        assert_eq!(b, pixel(scaled.as_ref(), 1, 0));
+        assert_eq!(b, pixel(scaled.as_ref(), 2, 1));
+        assert_eq!(w, pixel(scaled.as_ref(), 1, 1));
+        assert_eq!(w, pixel(scaled.as_ref(), 3, 0));
+        let scaled = scale2x(&red_green()).unwrap();
+        assert_eq!([255, 0, 0, 255], pixel(scaled.as_ref(), 1, 1));
+        assert_eq!([0, 255, 0, 255], pixel(scaled.as_ref(), 2, 0));
+    }
+
+    #[test]
+    fn laplacian_test() {
+        let mut surface = SoftwareSurface::new((3, 3));
+        surface.raw_mut().unwrap().iter_mut().for_each(|c| *c = 100);
+        surface.raw_mut().unwrap()[16..20].copy_from_slice(&[110, 100, 90, 200]);
+        let filtered = laplacian(&surface).unwrap();
+        assert_eq!([80, 0, 0, 200], pixel(filtered.as_ref(), 1, 1));
+        assert_eq!([0, 0, 10, 100], pixel(filtered.as_ref(), 0, 0));
+        assert_eq!([0, 0, 10, 100], pixel(filtered.as_ref(), 1, 0));
+    }
+
+    #[test]
+    fn software_transform_test() {
+        let transform: Box<dyn Transform> = Box::new(SoftwareTransform {});
+        assert_eq!((4, 2), transform.scale2x(&red_green()).unwrap().get_size());
+        assert_eq!((1, 2), transform.rotate(&red_green(), 90.0).unwrap().get_size());
+        assert_eq!((4, 2), transform.smooth_scale(&red_green(), (4, 2)).unwrap().get_size());
+    }
+}
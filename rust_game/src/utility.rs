@@ -1 +1,2 @@
+pub mod blend_utility;
 pub mod draw_utility;
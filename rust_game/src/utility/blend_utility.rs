@@ -0,0 +1,337 @@
+use crate::color::{Color, ColorU8};
+use crate::error::GameError;
+use crate::rectangle::Rect;
+use crate::surface::BlendMode;
+
+pub struct BlendUtility;
+
+impl BlendUtility {
+    fn mul_u8(a: u8, b: u8) -> u8 {
+        ((a as u32 * b as u32 + 127) / 255) as u8
+    }
+
+    fn add_u8(a: u8, b: u8) -> u8 {
+        a.saturating_add(b)
+    }
+
+    fn blend_none(dest: &mut [u8], source: &[u8]) {
+        dest.copy_from_slice(source);
+    }
+
+    fn blend_blend(dest: &mut [u8], source: &[u8]) {
+        let alpha = source[3];
+        let inverse_alpha = 255 - alpha;
+        for i in 0..3 {
+            dest[i] = BlendUtility::add_u8(BlendUtility::mul_u8(source[i], alpha), BlendUtility::mul_u8(dest[i], inverse_alpha));
+        }
+        dest[3] = BlendUtility::add_u8(alpha, BlendUtility::mul_u8(dest[3], inverse_alpha));
+    }
+
+    fn blend_add(dest: &mut [u8], source: &[u8]) {
+        let alpha = source[3];
+        for i in 0..3 {
+            dest[i] = BlendUtility::add_u8(BlendUtility::mul_u8(source[i], alpha), dest[i]);
+        }
+    }
+
+    fn blend_modulate(dest: &mut [u8], source: &[u8]) {
+        for i in 0..3 {
+            dest[i] = BlendUtility::mul_u8(source[i], dest[i]);
+        }
+    }
+
+    fn blend_multiply(dest: &mut [u8], source: &[u8]) {
+        let inverse_alpha = 255 - source[3];
+        for i in 0..3 {
+            dest[i] = BlendUtility::add_u8(BlendUtility::mul_u8(source[i], dest[i]), BlendUtility::mul_u8(dest[i], inverse_alpha));
+        }
+    }
+
+    fn blend_multiply_rgba(dest: &mut [u8], source: &[u8]) {
+        for i in 0..4 {
+            dest[i] = BlendUtility::mul_u8(source[i], dest[i]);
+        }
+    }
+
+    // composes the result of `mode` with the destination by the source alpha, like `blend_blend` composes the source color
+    fn blend_composed(dest: &mut [u8], source: &[u8], mode: fn(u8, u8) -> u8) {
+        let alpha = source[3];
+        let inverse_alpha = 255 - alpha;
+        for i in 0..3 {
+            let color = mode(source[i], dest[i]);
+            dest[i] = BlendUtility::add_u8(BlendUtility::mul_u8(color, alpha), BlendUtility::mul_u8(dest[i], inverse_alpha));
+        }
+        dest[3] = BlendUtility::add_u8(alpha, BlendUtility::mul_u8(dest[3], inverse_alpha));
+    }
+
+    fn blend_screen(dest: &mut [u8], source: &[u8]) {
+        BlendUtility::blend_composed(dest, source, |s, d| 255 - BlendUtility::mul_u8(255 - s, 255 - d));
+    }
+
+    fn blend_overlay(dest: &mut [u8], source: &[u8]) {
+        BlendUtility::blend_composed(dest, source, |s, d| match d < 128 {
+            true => ((2 * s as u32 * d as u32 + 127) / 255) as u8,
+            false => 255 - ((2 * (255 - s as u32) * (255 - d as u32) + 127) / 255) as u8,
+        });
+    }
+
+    fn blend_subtract(dest: &mut [u8], source: &[u8]) {
+        let alpha = source[3];
+        for i in 0..3 {
+            dest[i] = dest[i].saturating_sub(BlendUtility::mul_u8(source[i], alpha));
+        }
+    }
+
+    fn blend_min(dest: &mut [u8], source: &[u8]) {
+        for i in 0..4 {
+            dest[i] = dest[i].min(source[i]);
+        }
+    }
+
+    fn blend_max(dest: &mut [u8], source: &[u8]) {
+        for i in 0..4 {
+            dest[i] = dest[i].max(source[i]);
+        }
+    }
+
+    fn blend_darken(dest: &mut [u8], source: &[u8]) {
+        BlendUtility::blend_composed(dest, source, |s, d| s.min(d));
+    }
+
+    fn blend_lighten(dest: &mut [u8], source: &[u8]) {
+        BlendUtility::blend_composed(dest, source, |s, d| s.max(d));
+    }
+
+    fn blend_difference(dest: &mut [u8], source: &[u8]) {
+        BlendUtility::blend_composed(dest, source, |s, d| s.abs_diff(d));
+    }
+
+    fn blend_premultiplied(dest: &mut [u8], source: &[u8]) {
+        let inverse_alpha = 255 - source[3];
+        for i in 0..4 {
+            dest[i] = BlendUtility::add_u8(source[i], BlendUtility::mul_u8(dest[i], inverse_alpha));
+        }
+    }
+
+    /// Sets the RGBA8 pixels of `dest_rect` in `dest` to the color, the rectangle must lie inside of the buffer.
+    pub fn fill_raw(dest: &mut [u8], dest_pitch: usize, dest_rect: &Rect, color: &dyn Color) {
+        let rgba = [color.r(), color.g(), color.b(), color.a()];
+        let (w, h) = (dest_rect.get_width().max(0) as usize, dest_rect.get_height().max(0) as usize);
+        for y in 0..h {
+            let start = (dest_rect.get_top() as usize + y) * dest_pitch + dest_rect.get_left() as usize * 4;
+            for pixel in dest[start..start + w * 4].chunks_exact_mut(4) {
+                pixel.copy_from_slice(&rgba);
+            }
+        }
+    }
+
+    /// Blends the RGBA8 pixels of `source_rect` in `source` onto `dest_rect` in `dest`.
+    ///
+    /// Both rectangles must have the same size and lie inside their buffers; `blit` implementations clip them beforehand.
+    /// The blend equations follow the SDL2 surface blend modes, `MultiplyRGBA` multiplies all 4 channels.
+    pub fn blend_raw(
+        dest: &mut [u8],
+        dest_pitch: usize,
+        dest_rect: &Rect,
+        source: &[u8],
+        source_pitch: usize,
+        source_rect: &Rect,
+        blend_mode: BlendMode,
+    ) -> Result<(), GameError> {
+        BlendUtility::blend_raw_keyed(dest, dest_pitch, dest_rect, source, source_pitch, source_rect, blend_mode, Option::None, 255)
+    }
+
+    /// Like `blend_raw`, with the color key and the alpha of the source surface.
+    ///
+    /// Source pixels whose RGB matches `color_key` are skipped and the alpha of the source pixels is multiplied by `alpha`,
+    /// in all blend modes, like the color key and the alpha modulation of SDL2 surfaces.
+    /// With `PremultipliedBlend` the color channels are multiplied by `alpha` as well.
+    #[allow(clippy::too_many_arguments)]
+    pub fn blend_raw_keyed(
+        dest: &mut [u8],
+        dest_pitch: usize,
+        dest_rect: &Rect,
+        source: &[u8],
+        source_pitch: usize,
+        source_rect: &Rect,
+        blend_mode: BlendMode,
+        color_key: Option<ColorU8>,
+        alpha: u8,
+    ) -> Result<(), GameError> {
+        let blend_pixel: fn(&mut [u8], &[u8]) = match blend_mode {
+            BlendMode::None => BlendUtility::blend_none,
+            BlendMode::Blend => BlendUtility::blend_blend,
+            BlendMode::Add => BlendUtility::blend_add,
+            BlendMode::Modulate => BlendUtility::blend_modulate,
+            BlendMode::Multiply => BlendUtility::blend_multiply,
+            BlendMode::MultiplyRGBA => BlendUtility::blend_multiply_rgba,
+            BlendMode::Screen => BlendUtility::blend_screen,
+            BlendMode::Overlay => BlendUtility::blend_overlay,
+            BlendMode::Subtract => BlendUtility::blend_subtract,
+            BlendMode::Min => BlendUtility::blend_min,
+            BlendMode::Max => BlendUtility::blend_max,
+            BlendMode::Darken => BlendUtility::blend_darken,
+            BlendMode::Lighten => BlendUtility::blend_lighten,
+            BlendMode::Difference => BlendUtility::blend_difference,
+            BlendMode::PremultipliedBlend => BlendUtility::blend_premultiplied,
+        };
+        let premultiplied = blend_mode == BlendMode::PremultipliedBlend;
+        let key = color_key.map(|key| [key.r(), key.g(), key.b()]);
+        let (w, h) = (dest_rect.get_width().max(0) as usize, dest_rect.get_height().max(0) as usize);
+        for y in 0..h {
+            let dest_start = (dest_rect.get_top() as usize + y) * dest_pitch + dest_rect.get_left() as usize * 4;
+            let source_start = (source_rect.get_top() as usize + y) * source_pitch + source_rect.get_left() as usize * 4;
+            let dest_row = &mut dest[dest_start..dest_start + w * 4];
+            let source_row = &source[source_start..source_start + w * 4];
+            for (dest_pixel, source_pixel) in dest_row.chunks_exact_mut(4).zip(source_row.chunks_exact(4)) {
+                if key.is_some_and(|key| source_pixel[..3] == key) {
+                    continue;
+                }
+                match (alpha, premultiplied) {
+                    (255, _) => blend_pixel(dest_pixel, source_pixel),
+                    (_, true) => {
+                        let mut pixel = [0u8; 4];
+                        for (channel, source_channel) in pixel.iter_mut().zip(source_pixel) {
+                            *channel = BlendUtility::mul_u8(*source_channel, alpha);
+                        }
+                        blend_pixel(dest_pixel, &pixel);
+                    }
+                    (_, false) => {
+                        let alpha = BlendUtility::mul_u8(source_pixel[3], alpha);
+                        blend_pixel(dest_pixel, &[source_pixel[0], source_pixel[1], source_pixel[2], alpha]);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod blend_utility_test {
+    use super::*;
+
+    fn blend_pixel(dest: [u8; 4], source: [u8; 4], blend_mode: BlendMode) -> [u8; 4] {
+        let mut dest = dest;
+        let rect = Rect::new(0, 0, 1, 1);
+        BlendUtility::blend_raw(&mut dest, 4, &rect, &source, 4, &rect, blend_mode).unwrap();
+        dest
+    }
+
+    #[test]
+    fn blend_none_test() {
+        assert_eq!([10, 20, 30, 40], blend_pixel([100, 100, 100, 255], [10, 20, 30, 40], BlendMode::None));
+    }
+
+    #[test]
+    fn blend_blend_test() {
+        assert_eq!([255, 0, 0, 255], blend_pixel([0, 0, 255, 255], [255, 0, 0, 255], BlendMode::Blend));
+        assert_eq!([0, 0, 255, 255], blend_pixel([0, 0, 255, 255], [255, 0, 0, 0], BlendMode::Blend));
+        assert_eq!([128, 0, 127, 255], blend_pixel([0, 0, 255, 255], [255, 0, 0, 128], BlendMode::Blend));
+        assert_eq!([128, 0, 0, 128], blend_pixel([0, 0, 0, 0], [255, 0, 0, 128], BlendMode::Blend));
+    }
+
+    #[test]
+    fn blend_add_test() {
+        assert_eq!([255, 150, 100, 200], blend_pixel([200, 100, 100, 200], [100, 50, 0, 255], BlendMode::Add));
+        assert_eq!([250, 125, 100, 200], blend_pixel([200, 100, 100, 200], [100, 50, 0, 128], BlendMode::Add));
+    }
+
+    #[test]
+    fn blend_modulate_test() {
+        assert_eq!([100, 50, 0, 200], blend_pixel([200, 100, 100, 200], [128, 128, 0, 0], BlendMode::Modulate));
+    }
+
+    #[test]
+    fn blend_multiply_test() {
+        assert_eq!([100, 50, 0, 200], blend_pixel([200, 100, 100, 200], [128, 128, 0, 255], BlendMode::Multiply));
+        assert_eq!([200, 100, 100, 200], blend_pixel([200, 100, 100, 200], [0, 0, 0, 0], BlendMode::Multiply));
+    }
+
+    #[test]
+    fn blend_multiply_rgba_test() {
+        assert_eq!([100, 50, 0, 100], blend_pixel([200, 100, 100, 200], [128, 128, 0, 128], BlendMode::MultiplyRGBA));
+    }
+
+    #[test]
+    fn blend_screen_test() {
+        assert_eq!([255, 128, 64, 255], blend_pixel([255, 0, 64, 255], [0, 128, 0, 255], BlendMode::Screen));
+        assert_eq!([222, 130, 161, 255], blend_pixel([200, 100, 100, 255], [100, 50, 100, 255], BlendMode::Screen));
+        assert_eq!([100, 100, 100, 255], blend_pixel([100, 100, 100, 255], [255, 255, 255, 0], BlendMode::Screen));
+    }
+
+    #[test]
+    fn blend_overlay_test() {
+        assert_eq!([0, 100, 255, 255], blend_pixel([0, 100, 255, 255], [128, 128, 128, 255], BlendMode::Overlay));
+        assert_eq!([0, 200, 255, 255], blend_pixel([0, 100, 255, 255], [255, 255, 0, 255], BlendMode::Overlay));
+        assert_eq!([0, 0, 255, 255], blend_pixel([0, 100, 200, 255], [255, 0, 255, 255], BlendMode::Overlay));
+    }
+
+    #[test]
+    fn blend_subtract_test() {
+        assert_eq!([100, 50, 0, 200], blend_pixel([200, 100, 50, 200], [100, 50, 100, 255], BlendMode::Subtract));
+        assert_eq!([150, 75, 0, 200], blend_pixel([200, 100, 50, 200], [100, 50, 100, 128], BlendMode::Subtract));
+    }
+
+    #[test]
+    fn blend_min_test() {
+        assert_eq!([10, 100, 0, 128], blend_pixel([10, 200, 0, 255], [20, 100, 50, 128], BlendMode::Min));
+    }
+
+    #[test]
+    fn blend_max_test() {
+        assert_eq!([20, 200, 50, 255], blend_pixel([10, 200, 0, 255], [20, 100, 50, 128], BlendMode::Max));
+    }
+
+    #[test]
+    fn blend_darken_test() {
+        assert_eq!([10, 100, 0, 255], blend_pixel([10, 200, 0, 255], [20, 100, 50, 255], BlendMode::Darken));
+        assert_eq!([10, 150, 0, 255], blend_pixel([10, 200, 0, 255], [20, 100, 50, 128], BlendMode::Darken));
+    }
+
+    #[test]
+    fn blend_lighten_test() {
+        assert_eq!([20, 200, 50, 255], blend_pixel([10, 200, 0, 255], [20, 100, 50, 255], BlendMode::Lighten));
+        assert_eq!([15, 200, 25, 255], blend_pixel([10, 200, 0, 255], [20, 100, 50, 128], BlendMode::Lighten));
+    }
+
+    #[test]
+    fn blend_difference_test() {
+        assert_eq!([10, 100, 50, 255], blend_pixel([10, 200, 0, 255], [20, 100, 50, 255], BlendMode::Difference));
+        assert_eq!([128, 128, 128, 128], blend_pixel([0, 0, 0, 0], [255, 255, 255, 128], BlendMode::Difference));
+    }
+
+    #[test]
+    fn blend_premultiplied_test() {
+        assert_eq!([255, 0, 0, 255], blend_pixel([0, 0, 255, 255], [255, 0, 0, 255], BlendMode::PremultipliedBlend));
+        assert_eq!([128, 0, 127, 255], blend_pixel([0, 0, 255, 255], [128, 0, 0, 128], BlendMode::PremultipliedBlend));
+        assert_eq!([50, 0, 255, 255], blend_pixel([0, 0, 255, 255], [50, 0, 0, 0], BlendMode::PremultipliedBlend));
+    }
+
+    #[test]
+    fn blend_premultiplied_alpha_test() {
+        let mut dest = [0, 0, 255, 255];
+        let rect = Rect::new(0, 0, 1, 1);
+        BlendUtility::blend_raw_keyed(&mut dest, 4, &rect, &[255, 0, 0, 255], 4, &rect, BlendMode::PremultipliedBlend, None, 128).unwrap();
+        assert_eq!([128, 0, 127, 255], dest);
+    }
+
+    #[test]
+    fn blend_region_test() {
+        let mut dest = vec![0u8; 3 * 2 * 4];
+        let source = vec![255u8; 2 * 2 * 4];
+        BlendUtility::blend_raw(&mut dest, 12, &Rect::new(1, 1, 2, 1), &source, 8, &Rect::new(0, 1, 2, 1), BlendMode::None).unwrap();
+        assert_eq!(vec![0u8; 16], dest[..16].to_vec());
+        assert_eq!(vec![255u8; 8], dest[16..].to_vec());
+    }
+
+    #[test]
+    fn blend_keyed_test() {
+        let mut dest = vec![0u8; 2 * 4];
+        let source = vec![255, 0, 255, 255, 255, 0, 0, 200];
+        let rect = Rect::new(0, 0, 2, 1);
+        let color_key = Some(ColorU8::new_rgba(255, 0, 255, 0));
+        BlendUtility::blend_raw_keyed(&mut dest, 8, &rect, &source, 8, &rect, BlendMode::None, color_key, 128).unwrap();
+        assert_eq!(vec![0, 0, 0, 0, 255, 0, 0, 100], dest);
+    }
+}
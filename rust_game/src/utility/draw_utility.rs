@@ -1,8 +1,8 @@
 use crate::color::Color;
 use crate::context::Context;
+use crate::error::GameError;
 use crate::rectangle::Rect;
 use crate::surface::{BlendMode, Surface};
-use std::error::Error;
 use std::ops::Range;
 
 pub struct DrawUtility;
@@ -27,13 +27,13 @@ impl DrawUtility {
         color: &dyn Color,
         rectangle: Rect,
         blend_mode: BlendMode,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let (w, h) = rectangle.get_size();
         let (x, y) = rectangle.get_top_left();
         let mut blend_surface = context.new_surface_alpha_from_size((w as u32, h as u32))?;
         let _ = context.draw()?.rectangle(blend_surface.as_mut(), antialias, color, rectangle.move_(-x, -y), -1)?;
         surface.blit(blend_surface.as_ref(), (x, y), blend_mode)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     pub fn blit_circle(
@@ -44,14 +44,14 @@ impl DrawUtility {
         center: (i32, i32),
         radius: i32,
         blend_mode: BlendMode,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let rectangle = Rect::new(center.0 - radius as i32, center.1 - radius as i32, radius * 2, radius * 2);
         let (w, h) = rectangle.get_size();
         let (x, y) = rectangle.get_top_left();
         let mut blend_surface = context.new_surface_alpha_from_size((w as u32, h as u32))?;
         let _ = context.draw()?.circle(blend_surface.as_mut(), antialias, color, (center.0 - x, center.1 - y), radius, -1)?;
         surface.blit(blend_surface.as_ref(), (x, y), blend_mode)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     pub fn blit_ellipse(
@@ -63,7 +63,7 @@ impl DrawUtility {
         size: (i32, i32),
         angle: f32,
         blend_mode: BlendMode,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let radius = size.0.max(size.1);
         let rectangle = Rect::new(center.0 - radius as i32, center.1 - radius as i32, radius * 2, radius * 2);
         let (w, h) = rectangle.get_size();
@@ -71,7 +71,7 @@ impl DrawUtility {
         let mut blend_surface = context.new_surface_alpha_from_size((w as u32, h as u32))?;
         let _ = context.draw()?.ellipse(blend_surface.as_mut(), antialias, color, (center.0 - x, center.1 - y), size, angle, -1)?;
         surface.blit(blend_surface.as_ref(), (x, y), blend_mode)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     pub fn blit_arc(
@@ -83,14 +83,14 @@ impl DrawUtility {
         radius: i32,
         arc_angle: Range<f32>,
         blend_mode: BlendMode,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let rectangle = Rect::new(center.0 - radius as i32, center.1 - radius as i32, radius * 2, radius * 2);
         let (w, h) = rectangle.get_size();
         let (x, y) = rectangle.get_top_left();
         let mut blend_surface = context.new_surface_alpha_from_size((w as u32, h as u32))?;
         let _ = context.draw()?.arc(blend_surface.as_mut(), antialias, color, (center.0 - x, center.1 - y), radius, arc_angle, -1)?;
         surface.blit(blend_surface.as_ref(), (x, y), blend_mode)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     pub fn blit_elliptical_arc(
@@ -103,7 +103,7 @@ impl DrawUtility {
         angle: f32,
         arc_angle: Range<f32>,
         blend_mode: BlendMode,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let radius = size.0.max(size.1);
         let rectangle = Rect::new(center.0 - radius as i32, center.1 - radius as i32, radius * 2, radius * 2);
         let (w, h) = rectangle.get_size();
@@ -120,7 +120,7 @@ impl DrawUtility {
             -1,
         )?;
         surface.blit(blend_surface.as_ref(), (x, y), blend_mode)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     pub fn blit_polygon(
@@ -130,13 +130,13 @@ impl DrawUtility {
         color: &dyn Color,
         points: &Vec<(i32, i32)>,
         blend_mode: BlendMode,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let rectangle = DrawUtility::tuple_vec_enclosing_rectangle(points);
         let (w, h) = rectangle.get_size();
         let (x, y) = rectangle.get_top_left();
         let mut blend_surface = context.new_surface_alpha_from_size((w as u32, h as u32))?;
         let _ = context.draw()?.polygon(blend_surface.as_mut(), antialias, color, &DrawUtility::transform_points(points, (-x, -y)), -1)?;
         surface.blit(blend_surface.as_ref(), (x, y), blend_mode)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 }
@@ -1,8 +1,9 @@
 use crate::context::Sdl2Context;
 use crate::surface::Sdl2Surface;
 use rust_game::canvas::Canvas;
+use rust_game::error::GameError;
+use rust_game::rectangle::Rect;
 use rust_game::surface::Surface;
-use std::error::Error;
 use std::sync::Arc;
 extern crate sdl2;
 
@@ -13,8 +14,8 @@ pub struct Sdl2Canvas {
 }
 
 impl Sdl2Canvas {
-    pub fn new(context: &Sdl2Context) -> Result<Sdl2Canvas, Box<dyn Error>> {
-        let window = context.video_subsystem.window("rust-sdl2 demo", 800, 600).position_centered().build()?;
+    pub fn new(context: &Sdl2Context) -> Result<Sdl2Canvas, GameError> {
+        let window = context.video_subsystem.window("rust-sdl2 demo", 800, 600).position_centered().build().map_err(GameError::backend)?;
         let event_pump = context.sdl_context.event_pump()?;
         let window_surface = window.surface(&event_pump)?;
         let canvas_surface =
@@ -32,11 +33,28 @@ impl Canvas for Sdl2Canvas {
         &mut self.canvas_surface
     }
 
-    fn update(&mut self) -> Result<(), Box<dyn Error>> {
+    fn update(&mut self) -> Result<(), GameError> {
         let event_pump = self.sdl_context.event_pump()?;
         let mut window_surface = self.window.surface(&event_pump)?;
         self.canvas_surface.surface.blit(Option::None, &mut window_surface, Option::None)?;
         window_surface.update_window()?;
         Ok(())
     }
+
+    fn update_rects(&mut self, rects: &[Rect]) -> Result<(), GameError> {
+        let event_pump = self.sdl_context.event_pump()?;
+        let mut window_surface = self.window.surface(&event_pump)?;
+        let canvas_rect = self.canvas_surface.get_rect();
+        let sdl2_rects: Vec<sdl2::rect::Rect> = rects
+            .iter()
+            .map(|rect| canvas_rect.clip(rect))
+            .filter(|rect| rect.get_width() > 0 && rect.get_height() > 0)
+            .map(|rect| Sdl2Surface::rect_to_sdl2_rect(&rect))
+            .collect();
+        for sdl2_rect in sdl2_rects.iter() {
+            self.canvas_surface.surface.blit(Some(*sdl2_rect), &mut window_surface, Some(*sdl2_rect))?;
+        }
+        window_surface.update_window_rects(&sdl2_rects)?;
+        Ok(())
+    }
 }
@@ -1,17 +1,25 @@
 use crate::canvas::Sdl2Canvas;
+#[cfg(feature = "opencv")]
 use crate::draw::Sdl2Draw;
 use crate::events::Sdl2Events;
 use crate::image::Sdl2Image;
 use crate::surface::Sdl2Surface;
+#[cfg(feature = "opencv")]
+use crate::transform::Sdl2Transform;
 use rust_game::canvas::Canvas;
 use rust_game::color::Color;
 use rust_game::context::{Context, ContextData};
+#[cfg(not(feature = "opencv"))]
+use rust_game::draw::software_draw::SoftwareDraw;
 use rust_game::draw::Draw;
+use rust_game::error::GameError;
 use rust_game::events::Events;
 use rust_game::image::Image;
 use rust_game::surface::{Surface, SurfaceBuilder};
 use rust_game::time::{Time, TimeStd};
-use std::error::Error;
+#[cfg(not(feature = "opencv"))]
+use rust_game::transform::SoftwareTransform;
+use rust_game::transform::Transform;
 use std::sync::Arc;
 extern crate sdl2;
 
@@ -22,7 +30,7 @@ pub struct Sdl2Context {
 }
 
 impl Sdl2Context {
-    pub fn new() -> Result<Sdl2Context, Box<dyn Error>> {
+    pub fn new() -> Result<Sdl2Context, GameError> {
         let sdl_context = sdl2::init()?;
         let video_subsystem = sdl_context.video()?;
         Ok(Sdl2Context {
@@ -34,11 +42,11 @@ impl Sdl2Context {
 }
 
 impl SurfaceBuilder for Sdl2Context {
-    fn new_surface_alpha(size: (u32, u32)) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    fn new_surface_alpha(size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
         Sdl2Surface::new_alpha(size)
     }
 
-    fn new_surface_with_color(size: (u32, u32), color: &dyn Color) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    fn new_surface_with_color(size: (u32, u32), color: &dyn Color) -> Result<Box<dyn Surface>, GameError> {
         let mut surface = Sdl2Surface::new_alpha(size)?;
         surface.fill(color)?;
         Ok(surface)
@@ -46,22 +54,35 @@ impl SurfaceBuilder for Sdl2Context {
 }
 
 impl Context for Sdl2Context {
-    fn new_canvas(&self) -> Result<Box<dyn Canvas>, Box<dyn Error>> {
+    fn new_canvas(&self) -> Result<Box<dyn Canvas>, GameError> {
         Ok(Box::new(Sdl2Canvas::new(&self)?))
     }
-    fn events(&self) -> Result<Box<dyn Events>, Box<dyn Error>> {
+    fn events(&self) -> Result<Box<dyn Events>, GameError> {
         Ok(Box::new(Sdl2Events::from(&self)?))
     }
-    fn time(&self) -> Result<Box<dyn Time>, Box<dyn Error>> {
+    fn time(&self) -> Result<Box<dyn Time>, GameError> {
         Ok(Box::new(TimeStd::from(self.context_data.clone())?))
     }
-    fn new_surface_alpha_from_size(&self, size: (u32, u32)) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    fn new_surface_alpha_from_size(&self, size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
         Sdl2Surface::new_alpha(size)
     }
-    fn draw(&self) -> Result<Box<dyn Draw>, Box<dyn Error>> {
+    #[cfg(feature = "opencv")]
+    fn draw(&self) -> Result<Box<dyn Draw>, GameError> {
         Sdl2Draw::new()
     }
-    fn image(&self) -> Result<Box<dyn Image>, Box<dyn Error>> {
+    #[cfg(not(feature = "opencv"))]
+    fn draw(&self) -> Result<Box<dyn Draw>, GameError> {
+        SoftwareDraw::new()
+    }
+    fn image(&self) -> Result<Box<dyn Image>, GameError> {
         Sdl2Image::new()
     }
+    #[cfg(feature = "opencv")]
+    fn transform(&self) -> Result<Box<dyn Transform>, GameError> {
+        Ok(Box::new(Sdl2Transform {}))
+    }
+    #[cfg(not(feature = "opencv"))]
+    fn transform(&self) -> Result<Box<dyn Transform>, GameError> {
+        Ok(Box::new(SoftwareTransform {}))
+    }
 }
@@ -4,15 +4,15 @@ use opencv::imgproc;
 use opencv::types;
 use rust_game::color::Color;
 use rust_game::draw::Draw;
+use rust_game::error::GameError;
 use rust_game::rectangle::Rect;
 use rust_game::surface::Surface;
-use std::error::Error;
 use std::ops::Range;
 
 pub struct Sdl2Draw {}
 
 impl Sdl2Draw {
-    pub fn new() -> Result<Box<dyn Draw>, Box<dyn Error>> {
+    pub fn new() -> Result<Box<dyn Draw>, GameError> {
         Ok(Box::new(Sdl2Draw {}))
     }
 }
@@ -63,6 +63,12 @@ impl Sdl2Draw {
         Rect::new_from_points((min_x, min_y), (max_x, max_y))
     }
 
+    // matrix of the clip rectangle of the surface and the offset of the coordinates on the matrix
+    unsafe fn clip_to_opencv_mat(surface: &dyn Surface, offset: (i32, i32)) -> Result<Option<(core::Mat, (i32, i32))>, GameError> {
+        let clip = surface.get_clip();
+        Ok(opencv_util::surface_clip_to_opencv_mat(surface)?.map(|mat| (mat, (offset.0 - clip.x, offset.1 - clip.y))))
+    }
+
     fn rectangle_opencv(
         surface: &mut dyn Surface,
         offset: (i32, i32),
@@ -70,9 +76,12 @@ impl Sdl2Draw {
         color: &dyn Color,
         rectangle: Rect,
         line_width: i32,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), GameError> {
         unsafe {
-            let mut mat = opencv_util::surface_to_opencv_mat(surface)?;
+            let (mut mat, offset) = match Sdl2Draw::clip_to_opencv_mat(surface, offset)? {
+                Some(clip_mat) => clip_mat,
+                None => return Ok(()),
+            };
             let line_type = if antialias { imgproc::LINE_AA } else { imgproc::LINE_8 };
             let shift = 0;
             imgproc::rectangle(
@@ -82,7 +91,8 @@ impl Sdl2Draw {
                 line_width,
                 line_type,
                 shift,
-            )?;
+            )
+            .map_err(GameError::backend)?;
         };
         Ok(())
     }
@@ -95,9 +105,12 @@ impl Sdl2Draw {
         center: (i32, i32),
         radius: i32,
         line_width: i32,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), GameError> {
         unsafe {
-            let mut mat = opencv_util::surface_to_opencv_mat(surface)?;
+            let (mut mat, offset) = match Sdl2Draw::clip_to_opencv_mat(surface, offset)? {
+                Some(clip_mat) => clip_mat,
+                None => return Ok(()),
+            };
             let line_type = if antialias { imgproc::LINE_AA } else { imgproc::FILLED };
             let shift = 0;
             imgproc::circle(
@@ -108,7 +121,8 @@ impl Sdl2Draw {
                 line_width,
                 line_type,
                 shift,
-            )?;
+            )
+            .map_err(GameError::backend)?;
         }
         Ok(())
     }
@@ -123,9 +137,12 @@ impl Sdl2Draw {
         angle: f32,
         arc_angle: Range<f32>,
         line_width: i32,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), GameError> {
         unsafe {
-            let mut mat = opencv_util::surface_to_opencv_mat(surface)?;
+            let (mut mat, offset) = match Sdl2Draw::clip_to_opencv_mat(surface, offset)? {
+                Some(clip_mat) => clip_mat,
+                None => return Ok(()),
+            };
             let line_type = if antialias { imgproc::LINE_AA } else { imgproc::FILLED };
             let shift = 0;
             imgproc::ellipse(
@@ -139,7 +156,8 @@ impl Sdl2Draw {
                 line_width,
                 line_type,
                 shift,
-            )?;
+            )
+            .map_err(GameError::backend)?;
         }
         Ok(())
     }
@@ -150,9 +168,12 @@ impl Sdl2Draw {
         antialias: bool,
         color: &dyn Color,
         points: &Vec<(i32, i32)>,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), GameError> {
         unsafe {
-            let mut mat = opencv_util::surface_to_opencv_mat(surface)?;
+            let (mut mat, offset) = match Sdl2Draw::clip_to_opencv_mat(surface, offset)? {
+                Some(clip_mat) => clip_mat,
+                None => return Ok(()),
+            };
             let line_type = if antialias { imgproc::LINE_AA } else { imgproc::FILLED };
             let shift = 0;
             imgproc::fill_poly(
@@ -162,7 +183,8 @@ impl Sdl2Draw {
                 line_type,
                 shift,
                 Sdl2Draw::tuple_to_opencv_point((0, 0), (0, 0)),
-            )?;
+            )
+            .map_err(GameError::backend)?;
         }
         Ok(())
     }
@@ -176,9 +198,9 @@ impl Draw for Sdl2Draw {
         color: &dyn Color,
         rectangle: Rect,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         Sdl2Draw::rectangle_opencv(surface, (0, 0), antialias, color, rectangle.clone(), line_width)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     fn circle(
@@ -189,10 +211,10 @@ impl Draw for Sdl2Draw {
         center: (i32, i32),
         radius: i32,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let rectangle = Rect::new(center.0 - radius as i32, center.1 - radius as i32, radius * 2, radius * 2);
         Sdl2Draw::circle_opencv(surface, (0, 0), antialias, color, center, radius, line_width)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     fn ellipse(
@@ -204,11 +226,11 @@ impl Draw for Sdl2Draw {
         size: (i32, i32),
         angle: f32,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let radius = size.0.max(size.1);
         let rectangle = Rect::new(center.0 - radius as i32, center.1 - radius as i32, radius * 2, radius * 2);
         Sdl2Draw::elliptical_arc_opencv(surface, (0, 0), antialias, color, center, size, angle, 0.0..360.0, line_width)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     fn arc(
@@ -220,10 +242,10 @@ impl Draw for Sdl2Draw {
         radius: i32,
         arc_angle: Range<f32>,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let rectangle = Rect::new(center.0 - radius as i32, center.1 - radius as i32, radius * 2, radius * 2);
         Sdl2Draw::elliptical_arc_opencv(surface, (0, 0), antialias, color, center, (radius, radius), 0.0, arc_angle, line_width)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     fn elliptical_arc(
@@ -236,11 +258,11 @@ impl Draw for Sdl2Draw {
         angle: f32,
         arc_angle: Range<f32>,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         let radius = size.0.max(size.1);
         let rectangle = Rect::new(center.0 - radius as i32, center.1 - radius as i32, radius * 2, radius * 2);
         Sdl2Draw::elliptical_arc_opencv(surface, (0, 0), antialias, color, center, size, angle, arc_angle, line_width)?;
-        Ok(rectangle.clip(&surface.get_rect()))
+        Ok(rectangle.clip(&surface.get_clip()))
     }
 
     fn polygon(
@@ -250,8 +272,8 @@ impl Draw for Sdl2Draw {
         color: &dyn Color,
         points: &Vec<(i32, i32)>,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>> {
-        let surface_rectangle = surface.get_rect();
+    ) -> Result<Rect, GameError> {
+        let surface_rectangle = surface.get_clip();
         let rectangle = Sdl2Draw::tuple_vec_enclosing_rectangle(points);
         if line_width > 0 {
             self.lines(surface, antialias, color, true, points, line_width)?;
@@ -269,22 +291,26 @@ impl Draw for Sdl2Draw {
         start: (i32, i32),
         end: (i32, i32),
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         unsafe {
-            let mut mat = opencv_util::surface_to_opencv_mat(surface)?;
+            let (mut mat, offset) = match Sdl2Draw::clip_to_opencv_mat(surface, (0, 0))? {
+                Some(clip_mat) => clip_mat,
+                None => return Ok(surface.get_clip()),
+            };
             let line_type = if antialias { imgproc::LINE_AA } else { imgproc::LINE_8 };
             let shift = 0;
             imgproc::line(
                 &mut mat,
-                Sdl2Draw::tuple_to_opencv_point(start, (0, 0)),
-                Sdl2Draw::tuple_to_opencv_point(end, (0, 0)),
+                Sdl2Draw::tuple_to_opencv_point(start, offset),
+                Sdl2Draw::tuple_to_opencv_point(end, offset),
                 Sdl2Draw::color_to_opencv_scalar(color),
                 line_width,
                 line_type,
                 shift,
-            )?;
+            )
+            .map_err(GameError::backend)?;
         }
-        Ok(Rect::new_from_points(start, end).clip(&surface.get_rect()))
+        Ok(Rect::new_from_points(start, end).clip(&surface.get_clip()))
     }
 
     fn lines(
@@ -295,21 +321,25 @@ impl Draw for Sdl2Draw {
         close: bool,
         points: &Vec<(i32, i32)>,
         line_width: i32,
-    ) -> Result<Rect, Box<dyn Error>> {
+    ) -> Result<Rect, GameError> {
         unsafe {
-            let mut mat = opencv_util::surface_to_opencv_mat(surface)?;
+            let (mut mat, offset) = match Sdl2Draw::clip_to_opencv_mat(surface, (0, 0))? {
+                Some(clip_mat) => clip_mat,
+                None => return Ok(surface.get_clip()),
+            };
             let line_type = if antialias { imgproc::LINE_AA } else { imgproc::LINE_8 };
             let shift = 0;
             imgproc::polylines(
                 &mut mat,
-                &Sdl2Draw::tuple_vec_to_opencv_vector_of_points(points, (0, 0)),
+                &Sdl2Draw::tuple_vec_to_opencv_vector_of_points(points, offset),
                 close,
                 Sdl2Draw::color_to_opencv_scalar(color),
                 line_width,
                 line_type,
                 shift,
-            )?;
+            )
+            .map_err(GameError::backend)?;
         }
-        Ok(Sdl2Draw::tuple_vec_enclosing_rectangle(points).clip(&surface.get_rect()))
+        Ok(Sdl2Draw::tuple_vec_enclosing_rectangle(points).clip(&surface.get_clip()))
     }
 }
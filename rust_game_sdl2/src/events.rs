@@ -1,6 +1,6 @@
 use crate::context::Sdl2Context;
+use rust_game::error::GameError;
 use rust_game::events::{Event, Events};
-use std::error::Error;
 use std::sync::Arc;
 extern crate sdl2;
 use num_traits::FromPrimitive;
@@ -10,7 +10,7 @@ pub struct Sdl2Events {
 }
 
 impl Sdl2Events {
-    pub fn from(context: &Sdl2Context) -> Result<Sdl2Events, Box<dyn Error>> {
+    pub fn from(context: &Sdl2Context) -> Result<Sdl2Events, GameError> {
         Ok(Sdl2Events {
             sdl_context: context.sdl_context.clone(),
         })
@@ -22,7 +22,7 @@ impl Sdl2Events {
 }
 
 impl Events for Sdl2Events {
-    fn get(&mut self) -> Result<Vec<Event>, Box<dyn Error>> {
+    fn get(&mut self) -> Result<Vec<Event>, GameError> {
         let mut events = Vec::new();
         for event in self.sdl_context.event_pump()?.poll_iter() {
             match event {
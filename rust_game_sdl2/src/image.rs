@@ -6,9 +6,10 @@ use image::io::Reader;
 use image::{AnimationDecoder, DynamicImage, Frame, Frames, RgbaImage};
 //use image::ColorType;
 use resvg;
+use rust_game::error::GameError;
 use rust_game::image::Image;
+use rust_game::surface::software_surface::SoftwareSurface;
 use rust_game::surface::Surface;
-use std::error::Error;
 use std::ffi::OsStr;
 use std::fs::File;
 use std::path::Path;
@@ -20,38 +21,38 @@ struct PixelRGBA8888;
 pub struct Sdl2Image;
 
 impl Sdl2Image {
-    pub fn new() -> Result<Box<dyn Image>, Box<dyn Error>> {
+    pub fn new() -> Result<Box<dyn Image>, GameError> {
         Ok(Box::new(Sdl2Image {}))
     }
 }
 
 impl Sdl2Image {
-    pub fn load_image_to_raw(filename: &str) -> Result<(usize, usize, Vec<u8>), Box<dyn Error>> {
-        let rgba_image = Reader::open(filename)?.decode()?.to_rgba8();
+    pub fn load_image_to_raw(filename: &str) -> Result<(usize, usize, Vec<u8>), GameError> {
+        let rgba_image = Reader::open(filename)?.decode().map_err(GameError::image_decode)?.to_rgba8();
         Ok((rgba_image.width() as usize, rgba_image.height() as usize, DynamicImage::ImageRgba8(rgba_image).into_bytes()))
     }
 
-    pub fn raw_to_surface(size: (u32, u32), src_data: &Vec<u8>) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    pub fn raw_to_surface(size: (u32, u32), src_data: &Vec<u8>) -> Result<Box<dyn Surface>, GameError> {
         let mut surface = Sdl2Surface::new_alpha(size)?;
         surface.raw_mut()?.clone_from_slice(src_data);
         Ok(surface)
     }
 
-    pub fn load_image(&self, path: &Path) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    pub fn load_image(&self, path: &Path) -> Result<Box<dyn Surface>, GameError> {
         let rgba_image = match path.to_str() {
             Some(file_path) => Sdl2Image::load_image_to_raw(file_path)?,
-            _ => Err("cannot decode file path")?,
+            _ => Err(GameError::ImageDecode("cannot decode file path".to_string()))?,
         };
         Sdl2Image::raw_to_surface((rgba_image.0 as u32, rgba_image.1 as u32), &rgba_image.2)
     }
 
-    pub fn load_svg(&self, path: &Path) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    pub fn load_svg(&self, path: &Path) -> Result<Box<dyn Surface>, GameError> {
         let mut opt = usvg::Options::default();
         // Get file's absolute directory.
         opt.resources_dir = std::fs::canonicalize(path).ok().and_then(|p| p.parent().map(|p| p.to_path_buf()));
         opt.fontdb.load_system_fonts();
         let svg_data = std::fs::read(path)?;
-        let rtree = usvg::Tree::from_data(&svg_data, &opt.to_ref())?;
+        let rtree = usvg::Tree::from_data(&svg_data, &opt.to_ref()).map_err(GameError::image_decode)?;
         let pixmap_size = rtree.svg_node().size.to_screen_size();
         let (width, height) = (pixmap_size.width(), pixmap_size.height());
         let mut pixmap = tiny_skia::Pixmap::new(width, height).unwrap();
@@ -59,14 +60,14 @@ impl Sdl2Image {
         Sdl2Image::raw_to_surface((width, height), &pixmap.data().to_vec())
     }
 
-    pub fn load_image_frames(&self, path: &Path) -> Result<Vec<Box<dyn Surface>>, Box<dyn Error>> {
-        let mut frames = GifDecoder::new(File::open(path)?)?.into_frames();
+    pub fn load_image_frames(&self, path: &Path) -> Result<Vec<Box<dyn Surface>>, GameError> {
+        let mut frames = GifDecoder::new(File::open(path)?).map_err(GameError::image_decode)?.into_frames();
         //let images = frames.map(|frame| frame?.into_buffer()).collect();
         let mut images: Vec<Box<dyn Surface>> = Vec::new();
         loop {
             match frames.next() {
                 Some(frame) => {
-                    let rgba_image = frame?.into_buffer();
+                    let rgba_image = frame.map_err(GameError::image_decode)?.into_buffer();
                     let image = Sdl2Image::raw_to_surface(
                         (rgba_image.width() as u32, rgba_image.height() as u32),
                         &DynamicImage::ImageRgba8(rgba_image).into_bytes(),
@@ -79,37 +80,37 @@ impl Sdl2Image {
         Ok(images)
     }
 
-    fn save_image(&self, surface: &dyn Surface, path: &Path) -> Result<(), Box<dyn Error>> {
+    fn save_image(&self, surface: &dyn Surface, path: &Path) -> Result<(), GameError> {
         let w = surface.get_width();
         let h = surface.get_height();
-        match RgbaImage::from_raw(w, h, surface.raw()?.to_vec()) {
-            Some(image) => image.save(path)?,
-            _ => Err("cannot create image")?,
+        match RgbaImage::from_raw(w, h, SoftwareSurface::from_surface(surface)?.raw()?.to_vec()) {
+            Some(image) => image.save(path).map_err(GameError::image_encode)?,
+            _ => Err(GameError::ImageEncode("cannot create image".to_string()))?,
         };
         Ok(())
     }
 
-    pub fn save_image_frames(&self, frames: &Vec<Box<dyn Surface>>, path: &Path) -> Result<(), Box<dyn Error>> {
+    pub fn save_image_frames(&self, frames: &Vec<Box<dyn Surface>>, path: &Path) -> Result<(), GameError> {
         let mut gif_encoder = GifEncoder::new(File::create(path)?);
         let mut animation_frame_vec: Vec<Result<Frame, ImageError>> = Vec::new();
         for surface in frames.iter() {
             let w = surface.get_width();
             let h = surface.get_height();
             //Some(data) => gif_encoder.encode(data, w, h, ColorType::Rgba8)?,
-            match RgbaImage::from_raw(w, h, surface.raw()?.to_vec()) {
+            match RgbaImage::from_raw(w, h, SoftwareSurface::from_surface(surface.as_ref())?.raw()?.to_vec()) {
                 Some(image) => animation_frame_vec.push(Ok(Frame::new(image))),
                 //Some(image) => gif_encoder.encode_frame(Frame::new(image))?,
-                _ => Err("cannot create image")?,
+                _ => Err(GameError::ImageEncode("cannot create image".to_string()))?,
             };
         }
         let animation_frame = Frames::new(Box::new(animation_frame_vec.into_iter()));
-        gif_encoder.try_encode_frames(animation_frame)?;
+        gif_encoder.try_encode_frames(animation_frame).map_err(GameError::image_encode)?;
         Ok(())
     }
 }
 
 impl Image for Sdl2Image {
-    fn load(&self, path: &Path) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    fn load(&self, path: &Path) -> Result<Box<dyn Surface>, GameError> {
         let extension = path.extension().and_then(OsStr::to_str).unwrap().to_ascii_lowercase();
         match extension.as_str() {
             "svg" => self.load_svg(path),
@@ -117,15 +118,15 @@ impl Image for Sdl2Image {
         }
     }
 
-    fn load_frames(&self, path: &Path) -> Result<Vec<Box<dyn Surface>>, Box<dyn Error>> {
+    fn load_frames(&self, path: &Path) -> Result<Vec<Box<dyn Surface>>, GameError> {
         self.load_image_frames(path)
     }
 
-    fn save(&self, surface: &dyn Surface, path: &Path) -> Result<(), Box<dyn Error>> {
+    fn save(&self, surface: &dyn Surface, path: &Path) -> Result<(), GameError> {
         self.save_image(surface, path)
     }
 
-    fn save_frames(&self, frames: &Vec<Box<dyn Surface>>, path: &Path) -> Result<(), Box<dyn Error>> {
+    fn save_frames(&self, frames: &Vec<Box<dyn Surface>>, path: &Path) -> Result<(), GameError> {
         self.save_image_frames(frames, path)
     }
 }
@@ -2,7 +2,8 @@
 //!
 //! ## rust_game_Sdl2 bindings
 //!
-//! It is required to install SDL2 and OpenCV.
+//! It is required to install SDL2. OpenCV is required by the default feature `opencv`.
+//! Without the feature (`--no-default-features`), drawing and blending fall back to the pure Rust implementations of `rust_game`.
 //!
 //! ### Install SDL2
 //!
@@ -29,8 +30,12 @@
 
 mod canvas;
 pub mod context;
+#[cfg(feature = "opencv")]
 mod draw;
 mod events;
 mod image;
+#[cfg(feature = "opencv")]
 mod opencv_util;
 mod surface;
+#[cfg(feature = "opencv")]
+mod transform;
@@ -1,11 +1,27 @@
 use opencv::core;
+use rust_game::error::GameError;
 use rust_game::surface::Surface;
-use std::error::Error;
 
-pub unsafe fn surface_to_opencv_mat(surface: &dyn Surface) -> Result<core::Mat, Box<dyn Error>> {
+pub unsafe fn surface_to_opencv_mat(surface: &dyn Surface) -> Result<core::Mat, GameError> {
     let w = surface.get_width() as i32;
     let h = surface.get_height() as i32;
     let raw = surface.raw()?;
-    let step = (w * 4) as usize;
-    Ok(core::Mat::new_rows_cols_with_data(h, w, core::CV_8UC4, raw.as_ptr() as _, step)?)
+    let step = surface.get_pitch();
+    core::Mat::new_rows_cols_with_data(h, w, core::CV_8UC4, raw.as_ptr() as _, step).map_err(GameError::backend)
+}
+
+/// Matrix of the clip rectangle of the surface, `None` if the clip rectangle is empty.
+pub unsafe fn surface_clip_to_opencv_mat(surface: &dyn Surface) -> Result<Option<core::Mat>, GameError> {
+    let clip = surface.get_clip();
+    if clip.w <= 0 || clip.h <= 0 {
+        return Ok(Option::None);
+    }
+    let mat = surface_to_opencv_mat(surface)?;
+    let roi = core::Rect {
+        x: clip.x,
+        y: clip.y,
+        width: clip.w,
+        height: clip.h,
+    };
+    Ok(Some(core::Mat::roi(&mat, roi).map_err(GameError::backend)?))
 }
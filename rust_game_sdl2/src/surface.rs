@@ -1,33 +1,37 @@
+#[cfg(feature = "opencv")]
 use opencv::core;
-use rust_game::color::Color;
+use rust_game::color::{Color, ColorU8};
+use rust_game::error::GameError;
 use rust_game::rectangle::Rect;
 use rust_game::surface::{BlendMode, Surface};
+use rust_game::utility::blend_utility::BlendUtility;
 use sdl2;
 use std::any::Any;
-use std::error::Error;
 
 pub struct Sdl2Surface {
     pub surface: sdl2::surface::Surface<'static>,
 }
 
 impl Sdl2Surface {
+    #[cfg(feature = "opencv")]
     unsafe fn sdl2_surface_range_to_opencv_mat(
         sdl2_surface: &sdl2::surface::Surface<'static>,
         region: &Rect,
-    ) -> Result<core::Mat, Box<dyn Error>> {
+    ) -> Result<core::Mat, GameError> {
         let raw_surface = sdl2_surface.raw();
         let w = (*raw_surface).w as i32;
         let h = (*raw_surface).h as i32;
         let step = (w * 4) as usize;
-        let mat = core::Mat::new_rows_cols_with_data(h, w, core::CV_8UC4, (*raw_surface).pixels, step)?;
-        Ok(core::Mat::rowscols(
+        let mat = core::Mat::new_rows_cols_with_data(h, w, core::CV_8UC4, (*raw_surface).pixels, step).map_err(GameError::backend)?;
+        core::Mat::rowscols(
             &mat,
-            &core::Range::new(region.get_top(), region.get_bottom())?,
-            &core::Range::new(region.get_left(), region.get_right())?,
-        )?)
+            &core::Range::new(region.get_top(), region.get_bottom()).map_err(GameError::backend)?,
+            &core::Range::new(region.get_left(), region.get_right()).map_err(GameError::backend)?,
+        )
+        .map_err(GameError::backend)
     }
 
-    pub fn new_alpha(size: (u32, u32)) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    pub fn new_alpha(size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
         Ok(Box::new(Sdl2Surface {
             surface: sdl2::surface::Surface::new(size.0, size.1, sdl2::pixels::PixelFormatEnum::ABGR8888)?,
         }))
@@ -37,40 +41,71 @@ impl Sdl2Surface {
         Sdl2Surface { surface }
     }
 
+    /// Copies the raw data of a surface of another backend, e.g. a `SoftwareSurface`, to a new sdl2 surface.
+    pub fn from_raw_surface(surface: &dyn Surface) -> Result<Sdl2Surface, GameError> {
+        let (width, height) = surface.get_size();
+        let mut sdl2_surface = sdl2::surface::Surface::new(width, height, sdl2::pixels::PixelFormatEnum::ABGR8888)?;
+        let (source_pitch, pitch) = (surface.get_pitch(), sdl2_surface.pitch() as usize);
+        let source = surface.raw()?;
+        match sdl2_surface.without_lock_mut() {
+            Some(data) => {
+                for y in 0..height as usize {
+                    data[y * pitch..y * pitch + width as usize * 4]
+                        .copy_from_slice(&source[y * source_pitch..y * source_pitch + width as usize * 4]);
+                }
+            }
+            _ => Err(GameError::RawDataUnavailable)?,
+        }
+        let mut sdl2_surface = Sdl2Surface { surface: sdl2_surface };
+        sdl2_surface.set_colorkey(surface.get_colorkey().as_ref().map(|key| key as &dyn Color))?;
+        sdl2_surface.set_alpha(surface.get_alpha())?;
+        Ok(sdl2_surface)
+    }
+
     pub fn blend_sdl2_surface(
         dest_surface: &mut sdl2::surface::Surface<'static>,
         source_surface: &sdl2::surface::Surface<'static>,
         dest_rect: &Rect,
         src_rect: &Rect,
         blend_mode: BlendMode,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), GameError> {
         match blend_mode {
             BlendMode::MultiplyRGBA => {
-                Sdl2Surface::blend_sdl2_surface_opencv(dest_surface, source_surface, dest_rect, src_rect, blend_mode)
+                // the OpenCV implementation ignores the color key and the surface alpha
+                #[cfg(feature = "opencv")]
+                if source_surface.color_key().is_err() && source_surface.alpha_mod() == 255 {
+                    return Sdl2Surface::blend_sdl2_surface_opencv(dest_surface, source_surface, dest_rect, src_rect, blend_mode);
+                }
+                Sdl2Surface::blend_sdl2_surface_rust(dest_surface, source_surface, dest_rect, src_rect, blend_mode)
             }
-            _ => Sdl2Surface::blend_sdl2_surface_sdl2(
-                dest_surface,
-                source_surface,
-                Some(Sdl2Surface::rect_to_sdl2_rect(&dest_rect)),
-                Some(Sdl2Surface::rect_to_sdl2_rect(&src_rect)),
-                Sdl2Surface::blend_mode_to_sdl2_blend_mode(blend_mode),
-            ),
+            BlendMode::None | BlendMode::Blend | BlendMode::Add | BlendMode::Modulate | BlendMode::Multiply => {
+                Sdl2Surface::blend_sdl2_surface_sdl2(
+                    dest_surface,
+                    source_surface,
+                    Some(Sdl2Surface::rect_to_sdl2_rect(&dest_rect)),
+                    Some(Sdl2Surface::rect_to_sdl2_rect(&src_rect)),
+                    Sdl2Surface::blend_mode_to_sdl2_blend_mode(blend_mode)?,
+                )
+            }
+            // SDL2 surfaces have no custom blend modes
+            _ => Sdl2Surface::blend_sdl2_surface_rust(dest_surface, source_surface, dest_rect, src_rect, blend_mode),
         }
     }
 
-    fn rect_to_sdl2_rect(rect: &Rect) -> sdl2::rect::Rect {
+    pub(crate) fn rect_to_sdl2_rect(rect: &Rect) -> sdl2::rect::Rect {
         sdl2::rect::Rect::new(rect.get_left(), rect.get_top(), rect.get_width() as u32, rect.get_height() as u32)
     }
 
-    fn blend_mode_to_sdl2_blend_mode(blend_mode: BlendMode) -> sdl2::render::BlendMode {
-        match blend_mode {
+    fn blend_mode_to_sdl2_blend_mode(blend_mode: BlendMode) -> Result<sdl2::render::BlendMode, GameError> {
+        let sdl2_blend_mode = match blend_mode {
             BlendMode::None => sdl2::render::BlendMode::None,
             BlendMode::Blend => sdl2::render::BlendMode::Blend,
             BlendMode::Add => sdl2::render::BlendMode::Add,
             BlendMode::Modulate => sdl2::render::BlendMode::Mod,
             BlendMode::Multiply | BlendMode::MultiplyRGBA => sdl2::render::BlendMode::Mul,
-            _ => sdl2::render::BlendMode::Invalid,
-        }
+            _ => Err(GameError::UnsupportedBlendMode(blend_mode))?,
+        };
+        Ok(sdl2_blend_mode)
     }
 
     fn blend_sdl2_surface_sdl2(
@@ -79,37 +114,74 @@ impl Sdl2Surface {
         dest_rect: Option<sdl2::rect::Rect>,
         src_rect: Option<sdl2::rect::Rect>,
         blend_mode: sdl2::render::BlendMode,
-    ) -> Result<(), Box<dyn Error>> {
-        unsafe {
-            let const_ptr = source_surface as *const sdl2::surface::Surface<'static>;
-            let mut_ptr = const_ptr as *mut sdl2::surface::Surface<'static>;
-            let surface = &mut *mut_ptr;
-            surface.set_blend_mode(blend_mode)?;
-            source_surface.blit(src_rect, dest_surface, dest_rect)?;
-            surface.set_blend_mode(sdl2::render::BlendMode::Blend)?;
-        }
+    ) -> Result<(), GameError> {
+        Sdl2Surface::set_sdl2_surface_blend_mode(source_surface, blend_mode)?;
+        source_surface.blit(src_rect, dest_surface, dest_rect)?;
+        Sdl2Surface::set_sdl2_surface_blend_mode(source_surface, sdl2::render::BlendMode::Blend)?;
         Ok(())
     }
 
+    fn set_sdl2_surface_blend_mode(
+        surface: &sdl2::surface::Surface<'static>,
+        blend_mode: sdl2::render::BlendMode,
+    ) -> Result<(), GameError> {
+        // the source surface is only borrowed, so the blend mode is set on the raw SDL surface
+        let sdl2_blend_mode = match blend_mode {
+            sdl2::render::BlendMode::None => sdl2::sys::SDL_BlendMode::SDL_BLENDMODE_NONE,
+            sdl2::render::BlendMode::Blend => sdl2::sys::SDL_BlendMode::SDL_BLENDMODE_BLEND,
+            sdl2::render::BlendMode::Add => sdl2::sys::SDL_BlendMode::SDL_BLENDMODE_ADD,
+            sdl2::render::BlendMode::Mod => sdl2::sys::SDL_BlendMode::SDL_BLENDMODE_MOD,
+            sdl2::render::BlendMode::Mul => sdl2::sys::SDL_BlendMode::SDL_BLENDMODE_MUL,
+            sdl2::render::BlendMode::Invalid => sdl2::sys::SDL_BlendMode::SDL_BLENDMODE_INVALID,
+        };
+        match unsafe { sdl2::sys::SDL_SetSurfaceBlendMode(surface.raw(), sdl2_blend_mode) } {
+            0 => Ok(()),
+            _ => Err(sdl2::get_error())?,
+        }
+    }
+
+    #[cfg(feature = "opencv")]
     fn blend_sdl2_surface_opencv(
         dest_surface: &mut sdl2::surface::Surface<'static>,
         source_surface: &sdl2::surface::Surface<'static>,
         dest_rect: &Rect,
         src_rect: &Rect,
         blend_mode: BlendMode,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<(), GameError> {
         unsafe {
             match blend_mode {
                 BlendMode::MultiplyRGBA => {
                     let mut dest_mat = Sdl2Surface::sdl2_surface_range_to_opencv_mat(dest_surface, dest_rect)?;
                     let src_mat = Sdl2Surface::sdl2_surface_range_to_opencv_mat(source_surface, src_rect)?;
-                    core::multiply(&dest_mat.clone(), &src_mat, &mut dest_mat, 1.0 / 255.0, -1)?;
+                    core::multiply(&dest_mat.clone(), &src_mat, &mut dest_mat, 1.0 / 255.0, -1).map_err(GameError::backend)?;
                 }
-                _ => Err("not yet implemented")?,
+                _ => Err(GameError::UnsupportedBlendMode(blend_mode))?,
             }
         }
         Ok(())
     }
+
+    fn blend_sdl2_surface_rust(
+        dest_surface: &mut sdl2::surface::Surface<'static>,
+        source_surface: &sdl2::surface::Surface<'static>,
+        dest_rect: &Rect,
+        src_rect: &Rect,
+        blend_mode: BlendMode,
+    ) -> Result<(), GameError> {
+        let dest_pitch = dest_surface.pitch() as usize;
+        let source_pitch = source_surface.pitch() as usize;
+        let source_data = match source_surface.without_lock() {
+            Some(data) => data,
+            _ => Err(GameError::RawDataUnavailable)?,
+        };
+        let dest_data = match dest_surface.without_lock_mut() {
+            Some(data) => data,
+            _ => Err(GameError::RawDataUnavailable)?,
+        };
+        let color_key = source_surface.color_key().ok().map(|key| ColorU8::new_rgba(key.r, key.g, key.b, key.a));
+        let alpha = source_surface.alpha_mod();
+        BlendUtility::blend_raw_keyed(dest_data, dest_pitch, dest_rect, source_data, source_pitch, src_rect, blend_mode, color_key, alpha)
+    }
 }
 
 impl Surface for Sdl2Surface {
@@ -117,14 +189,12 @@ impl Surface for Sdl2Surface {
         self
     }
 
-    fn clone(&self) -> Result<Box<dyn Surface>, Box<dyn Error>> {
-        let mut surface_copy =
-            sdl2::surface::Surface::new(self.surface.width(), self.surface.height(), sdl2::pixels::PixelFormatEnum::ABGR8888)?;
-        self.surface.blit(Option::None, &mut surface_copy, Option::None)?;
-        Ok(Box::new(Sdl2Surface { surface: surface_copy }))
+    fn clone(&self) -> Result<Box<dyn Surface>, GameError> {
+        // a copy of the raw data, blitting would apply the color key and the alpha
+        Ok(Box::new(Sdl2Surface::from_raw_surface(self)?))
     }
 
-    fn modulate_surface_and_color(&self, color: &dyn Color) -> Result<Box<dyn Surface>, Box<dyn Error>> {
+    fn modulate_surface_and_color(&self, color: &dyn Color) -> Result<Box<dyn Surface>, GameError> {
         let mut color_surface = Sdl2Surface {
             surface: sdl2::surface::Surface::new(self.surface.width(), self.surface.height(), sdl2::pixels::PixelFormatEnum::ABGR8888)?,
         };
@@ -152,32 +222,88 @@ impl Surface for Sdl2Surface {
         Rect::new(0, 0, self.surface.width() as i32, self.surface.height() as i32)
     }
 
-    fn raw(&self) -> Result<&[u8], Box<dyn Error>> {
+    fn raw(&self) -> Result<&[u8], GameError> {
         match self.surface.without_lock() {
             Some(data) => Ok(data),
-            _ => Err("cannot retrieve raw data")?,
+            _ => Err(GameError::RawDataUnavailable)?,
         }
     }
 
-    fn raw_mut(&mut self) -> Result<&mut [u8], Box<dyn Error>> {
+    fn raw_mut(&mut self) -> Result<&mut [u8], GameError> {
         match self.surface.without_lock_mut() {
             Some(data) => Ok(data),
-            _ => Err("cannot retrieve raw data")?,
+            _ => Err(GameError::RawDataUnavailable)?,
         }
     }
 
-    fn fill(&mut self, color: &dyn Color) -> Result<(), Box<dyn Error>> {
+    fn get_pitch(&self) -> usize {
+        self.surface.pitch() as usize
+    }
+
+    fn fill(&mut self, color: &dyn Color) -> Result<(), GameError> {
         self.surface.fill_rect(Option::None, sdl2::pixels::Color::RGBA(color.r(), color.g(), color.b(), color.a()))?;
         Ok(())
     }
 
-    fn blit(&mut self, source_surface: &dyn Surface, position: (i32, i32), blend_mode: BlendMode) -> Result<Rect, Box<dyn Error>> {
+    fn set_colorkey(&mut self, color_key: Option<&dyn Color>) -> Result<(), GameError> {
+        match color_key {
+            Some(key) => self.surface.set_color_key(true, sdl2::pixels::Color::RGBA(key.r(), key.g(), key.b(), key.a()))?,
+            None => self.surface.set_color_key(false, sdl2::pixels::Color::RGBA(0, 0, 0, 0))?,
+        }
+        Ok(())
+    }
+
+    fn get_colorkey(&self) -> Option<ColorU8> {
+        self.surface.color_key().ok().map(|key| ColorU8::new_rgba(key.r, key.g, key.b, key.a))
+    }
+
+    fn set_alpha(&mut self, alpha: u8) -> Result<(), GameError> {
+        self.surface.set_alpha_mod(alpha);
+        Ok(())
+    }
+
+    fn get_alpha(&self) -> u8 {
+        self.surface.alpha_mod()
+    }
+
+    fn set_clip(&mut self, clip: Option<Rect>) -> Result<(), GameError> {
+        // `sdl2::rect::Rect` cannot be empty, so the raw SDL rectangle is used
+        let sdl_rect = clip.map(|clip| {
+            let clip = self.get_rect().clip(&clip);
+            sdl2::sys::SDL_Rect {
+                x: clip.x,
+                y: clip.y,
+                w: clip.w,
+                h: clip.h,
+            }
+        });
+        let sdl_rect_ptr = match &sdl_rect {
+            Some(sdl_rect) => sdl_rect as *const sdl2::sys::SDL_Rect,
+            None => std::ptr::null(),
+        };
+        unsafe { sdl2::sys::SDL_SetClipRect(self.surface.raw(), sdl_rect_ptr) };
+        Ok(())
+    }
+
+    fn get_clip(&self) -> Rect {
+        let clip = unsafe { (*self.surface.raw()).clip_rect };
+        Rect::new(clip.x, clip.y, clip.w, clip.h)
+    }
+
+    fn blit(&mut self, source_surface: &dyn Surface, position: (i32, i32), blend_mode: BlendMode) -> Result<Rect, GameError> {
         let src_rect = source_surface.get_rect().move_(position.0, position.1);
-        let dest_rect = self.get_rect().clip(&src_rect);
+        let dest_rect = self.get_clip().clip(&src_rect);
         let src_rect = src_rect.clip(&dest_rect).move_(-position.0, -position.1);
+        if dest_rect.w <= 0 || dest_rect.h <= 0 {
+            return Ok(dest_rect);
+        }
+        let converted_source_surface;
         let sdl2_source_surface: &Sdl2Surface = match source_surface.as_any().downcast_ref::<Sdl2Surface>() {
             Some(sdl2_source_surface) => sdl2_source_surface,
-            None => Err("not a sdl2 surface")?,
+            None => {
+                converted_source_surface = Sdl2Surface::from_raw_surface(source_surface)?;
+                &converted_source_surface
+            }
         };
         Sdl2Surface::blend_sdl2_surface(&mut self.surface, &sdl2_source_surface.surface, &dest_rect, &src_rect, blend_mode)?;
         Ok(dest_rect)
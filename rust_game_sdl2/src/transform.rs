@@ -0,0 +1,55 @@
+use crate::opencv_util;
+use crate::surface::Sdl2Surface;
+use opencv::core;
+use opencv::imgproc;
+use rust_game::error::GameError;
+use rust_game::surface::Surface;
+use rust_game::transform::{self, Transform};
+
+/// `Transform` implementation which flips and scales with OpenCV, the other transformations use the implementations of `rust_game`.
+pub struct Sdl2Transform {}
+
+impl Sdl2Transform {
+    // OpenCV cannot process empty images
+    fn is_empty(size: (u32, u32)) -> bool {
+        size.0 == 0 || size.1 == 0
+    }
+}
+
+impl Transform for Sdl2Transform {
+    fn flip(&self, surface: &dyn Surface, flip_x: bool, flip_y: bool) -> Result<Box<dyn Surface>, GameError> {
+        let flip_code = match (flip_x, flip_y) {
+            (true, false) => 1,
+            (false, true) => 0,
+            (true, true) => -1,
+            (false, false) => return surface.clone(),
+        };
+        if Sdl2Transform::is_empty(surface.get_size()) {
+            return transform::flip(surface, flip_x, flip_y);
+        }
+        let target = Sdl2Surface::new_alpha(surface.get_size())?;
+        unsafe {
+            let source_mat = opencv_util::surface_to_opencv_mat(surface)?;
+            let mut target_mat = opencv_util::surface_to_opencv_mat(target.as_ref())?;
+            core::flip(&source_mat, &mut target_mat, flip_code).map_err(GameError::backend)?;
+        }
+        Ok(target)
+    }
+
+    fn scale(&self, surface: &dyn Surface, size: (u32, u32)) -> Result<Box<dyn Surface>, GameError> {
+        if Sdl2Transform::is_empty(surface.get_size()) || Sdl2Transform::is_empty(size) {
+            return transform::scale(surface, size);
+        }
+        let target = Sdl2Surface::new_alpha(size)?;
+        unsafe {
+            let source_mat = opencv_util::surface_to_opencv_mat(surface)?;
+            let mut target_mat = opencv_util::surface_to_opencv_mat(target.as_ref())?;
+            let target_size = core::Size {
+                width: size.0 as i32,
+                height: size.1 as i32,
+            };
+            imgproc::resize(&source_mat, &mut target_mat, target_size, 0.0, 0.0, imgproc::INTER_NEAREST).map_err(GameError::backend)?;
+        }
+        Ok(target)
+    }
+}